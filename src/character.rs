@@ -1,15 +1,35 @@
+pub mod advantages;
 pub mod attributes;
 pub mod blood;
+pub mod bonds;
+pub mod clan;
+pub mod disciplines;
+pub mod experience;
+pub mod feeding;
+pub mod predator;
+pub mod rating;
+pub mod sea_of_time;
 pub mod skills;
 pub mod stats;
 
-use crate::character::blood::{BloodPotency, Generation, Hunger};
-use crate::character::stats::{Damage, Health, Humanity, Willpower};
+use crate::character::advantages::Advantages;
+use crate::character::blood::{BloodPotency, Generation, Hunger, RouseCheck, RouseOutcome};
+use crate::character::bonds::BloodBonds;
+use crate::character::clan::{Clan, Compulsion};
+use crate::character::disciplines::{Discipline, Disciplines};
+use crate::character::experience::{AdvanceError, Experience, LedgerEntry, Purchase};
+use crate::character::feeding::{Feeding, FeedingError, ResonanceRecord};
+use crate::character::predator::PredatorType;
+use crate::character::stats::{
+    Damage, DamageKind, Healing, Health, Humanity, TrackState, Willpower,
+};
+use crate::dice::pool::DicePool;
+use crate::dice::{Outcome, Roll};
 use anyhow::Result;
 pub use attributes::Attribute;
 use attributes::Attributes;
-use bon::builder;
 use bon::Builder;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use skills::Skills;
 use std::fs::File;
@@ -24,7 +44,7 @@ use std::path::Path;
 /// like using the `create` command or the GUI. (TODO: not implemented yet)
 ///
 /// You can provide [Attributes] and [Skills] or leave them blank (by explicitly passing [None]);
-/// with [None], the default values will be set (0 for attributes and (0, None) for skills;
+/// with [None], the default values will be set (0 for attributes and SkillRating::new(0) for skills;
 /// see [Skills].
 ///
 /// **Note**: We assume that a new character does not have any [Damage];
@@ -34,12 +54,30 @@ pub struct Character {
     pub character_name: String,
     pub chronicle: String,
 
+    /// Older sheets may not have a clan yet, so it stays optional.
+    #[serde(default)]
+    pub clan: Option<Clan>,
+    #[serde(default)]
+    pub predator_type: Option<PredatorType>,
+
     // TODO in the future, neither attributes nor skills should be unset, but for now we can
     //  live with them being all zeros.
     #[builder(default)]
     pub attributes: Attributes,
     #[builder(default)]
     pub skills: Skills,
+    #[serde(default)]
+    #[builder(default)]
+    pub disciplines: Disciplines,
+    #[serde(default)]
+    #[builder(default)]
+    pub advantages: Advantages,
+    #[serde(default)]
+    #[builder(default)]
+    pub experience: Experience,
+    #[serde(default)]
+    #[builder(default)]
+    pub blood_bonds: BloodBonds,
 
     // Initialize it with its `Default` value
     #[builder(skip)]
@@ -57,6 +95,13 @@ pub struct Character {
     pub generation: Generation,
     #[builder(default)]
     pub hunger: Hunger,
+    /// The resonance of the blood the character last fed on.
+    #[serde(default)]
+    pub resonance: Option<ResonanceRecord>,
+    /// Whether the clan compulsion has been triggered and not yet resolved.
+    #[serde(default)]
+    #[builder(default)]
+    pub compulsion_active: bool,
 }
 
 impl Character {
@@ -92,22 +137,302 @@ impl Character {
         println!("Chronicle: {}", self.chronicle);
 
         println!("Character: {}", self.character_name);
+        if let Some(clan) = &self.clan {
+            println!("Clan: {clan}");
+            if let Some(bane) = clan.bane(&self.blood_potency) {
+                println!(
+                    "Bane: {} (severity {}): {}",
+                    bane.name, bane.severity, bane.description
+                );
+            }
+            if let Some(compulsion) = clan.compulsion() {
+                println!(
+                    "Compulsion: {}{}: {}",
+                    compulsion.name,
+                    if self.compulsion_active {
+                        " (active)"
+                    } else {
+                        ""
+                    },
+                    compulsion.description
+                );
+            }
+        }
+
+        if let Some(predator_type) = &self.predator_type {
+            println!("Predator type: {predator_type}");
+        }
 
         // TODO: print all the fields? or just most important?
         println!("Attributes: {:?}", self.attributes);
         println!("Skills: {:?}", self.skills);
+        if !self.disciplines.is_empty() {
+            println!("Disciplines:");
+            for (discipline, rating) in self.disciplines.iter() {
+                let powers: Vec<&str> = rating.powers.iter().map(|p| p.name.as_str()).collect();
+                println!(
+                    "  {discipline} {}{}: {}",
+                    rating.dots,
+                    if self.is_in_clan(*discipline) {
+                        " (in-clan)"
+                    } else {
+                        ""
+                    },
+                    powers.join(", ")
+                );
+            }
+        }
+        if !self.advantages.is_empty() {
+            for (kind, advantages) in [
+                ("Merits", &self.advantages.merits),
+                ("Backgrounds", &self.advantages.backgrounds),
+                ("Flaws", &self.advantages.flaws),
+            ] {
+                let advantages: Vec<String> = advantages.iter().map(|a| a.to_string()).collect();
+                println!("{kind}: {}", advantages.join(", "));
+            }
+        }
+        for bond in self.blood_bonds.iter() {
+            println!("Blood bond: {bond}");
+        }
+        println!(
+            "Experience: {} earned, {} spent, {} available",
+            self.experience.earned(),
+            self.experience.spent(),
+            self.experience.available()
+        );
         println!(
-            "Health & Damage: {:?}",
-            Health::from_character(
-                self,
-                Some(self.damage.superficial),
-                Some(self.damage.aggravated)
-            )
+            "Health & Damage: {:?} ({})",
+            self.health(),
+            self.health().state()
         );
         // println!("Hunger: {:?}", self.hunger);
-        // TODO note: when using :? we print the Debug print, which gives `BloodPotency(1)` or similar;
-        //  we'll need to impl a Display that only prints the value or something that reads more nicely
-        println!("Blood Potency: {:?}", self.blood_potency);
+        if let Some(resonance) = &self.resonance {
+            println!("Resonance: {resonance}");
+        }
+        let effects = self.blood_potency.effects();
+        println!(
+            "Blood Potency: {} (surge +{}, mend {}, power bonus +{}, rouse re-roll up to level {})",
+            self.blood_potency,
+            effects.blood_surge,
+            effects.mend_amount,
+            effects.power_bonus,
+            effects.rouse_reroll_level
+        );
+    }
+
+    /// Whether a discipline is in-clan for this character. Characters without a clan
+    /// don't have any in-clan disciplines.
+    pub fn is_in_clan(&self, discipline: Discipline) -> bool {
+        self.clan.is_some_and(|c| c.is_in_clan(discipline))
+    }
+
+    /// Spend experience on a purchase, raising the trait and recording the purchase
+    /// in the ledger. Returns the cost.
+    pub fn advance(&mut self, purchase: Purchase) -> Result<u32, AdvanceError> {
+        let cost = purchase.cost(self)?;
+        let available = self.experience.available();
+        if cost > available {
+            return Err(AdvanceError::NotEnoughXp { cost, available });
+        }
+
+        match &purchase {
+            Purchase::Attribute(attr) => {
+                self.attributes[*attr] = (self.attributes[*attr].value() + 1).into()
+            }
+            Purchase::Skill(skill) => {
+                self.skills[*skill].dots = (self.skills[*skill].dots.value() + 1).into()
+            }
+            Purchase::Specialty { skill, specialty } => self
+                .skills
+                .add_specialty(*skill, specialty.as_str())
+                .expect("the specialty was checked by Purchase::cost"),
+            Purchase::Discipline(discipline) => self.disciplines.add_dot(*discipline),
+            Purchase::BloodPotency => {
+                self.blood_potency = (self.blood_potency.value() + 1).into();
+            }
+        }
+        self.experience.ledger.push(LedgerEntry::Spent {
+            amount: cost,
+            purchase,
+        });
+        Ok(cost)
+    }
+
+    /// Make a Rouse Check, raising Hunger if it fails. At Hunger 5 the blood can't be
+    /// roused at all, so no dice are rolled and the vampire is starving.
+    ///
+    /// `power_level` is the level of the discipline power the check is for, if any:
+    /// Blood Potency allows re-rolling a failed check for powers up to a certain level.
+    pub fn rouse_check(&mut self, power_level: Option<u8>, rng: &mut impl Rng) -> RouseCheck {
+        if self.hunger == 5 {
+            return RouseCheck {
+                dice: Vec::new(),
+                outcome: RouseOutcome::Starving,
+            };
+        }
+        let may_reroll = power_level
+            .is_some_and(|level| level <= self.blood_potency.effects().rouse_reroll_level);
+
+        let mut dice = vec![rng.gen_range(1..=10)];
+        if dice[0] < 6 && may_reroll {
+            dice.push(rng.gen_range(1..=10));
+        }
+
+        let outcome = if dice.iter().any(|&d| d >= 6) {
+            RouseOutcome::Passed
+        } else {
+            self.hunger.raise();
+            RouseOutcome::HungerRose(self.hunger.value())
+        };
+        RouseCheck { dice, outcome }
+    }
+
+    /// Feed, slaking Hunger as far as Blood Potency allows, and keep the vessel's
+    /// resonance on the sheet. Returns the new Hunger.
+    pub fn feed(&mut self, feeding: Feeding) -> Result<u8, FeedingError> {
+        feeding.validate()?;
+        let penalty = self.blood_potency.effects().feeding_penalty;
+        self.hunger = Hunger::new(feeding.hunger_after(self.hunger.value(), &penalty));
+        if let Some(resonance) = feeding.resonance {
+            self.resonance = Some(resonance);
+        }
+        Ok(self.hunger.value())
+    }
+
+    /// Mark stains on the Humanity track. Stains that don't fit deal aggravated
+    /// Willpower damage.
+    pub fn add_stains(&mut self, stains: u8) {
+        let overflow = self.humanity.add_stains(stains);
+        if overflow > 0 {
+            self.take_willpower_damage(DamageKind::Aggravated, overflow);
+        }
+    }
+
+    /// Roll to resist frenzy against the difficulty the Storyteller sets.
+    pub fn resist_frenzy(&mut self, difficulty: u8, rng: &mut impl Rng) -> FrenzyTest {
+        let pool = DicePool::frenzy(self);
+        let roll = pool.roll(rng);
+        let outcome = roll.outcome(difficulty);
+        let compulsion = self.trigger_compulsion(outcome);
+        FrenzyTest {
+            pool,
+            roll,
+            outcome,
+            compulsion,
+        }
+    }
+
+    /// A messy critical or a bestial failure triggers the clan compulsion, which stays
+    /// active until [Character::resolve_compulsion]. Returns the compulsion if this roll
+    /// triggered it.
+    pub fn trigger_compulsion(&mut self, outcome: Outcome) -> Option<Compulsion> {
+        if !matches!(outcome, Outcome::MessyCritical | Outcome::BestialFailure) {
+            return None;
+        }
+        let compulsion = self.clan?.compulsion()?;
+        self.compulsion_active = true;
+        Some(compulsion)
+    }
+
+    pub fn resolve_compulsion(&mut self) {
+        self.compulsion_active = false;
+    }
+
+    /// Mend superficial Health damage with a Rouse Check, healing as much as Blood
+    /// Potency allows. The damage heals whether the check passes or not, but a vampire
+    /// at Hunger 5 can't rouse the blood and heals nothing. Without superficial damage,
+    /// no Rouse Check is made.
+    pub fn mend(&mut self, rng: &mut impl Rng) -> Healing {
+        if self.damage.superficial == 0 {
+            return Healing {
+                rouse_checks: Vec::new(),
+                healed: 0,
+            };
+        }
+        let rouse = self.rouse_check(None, rng);
+        let healed = if rouse.outcome == RouseOutcome::Starving {
+            0
+        } else {
+            let healed = self
+                .damage
+                .superficial
+                .min(self.blood_potency.effects().mend_amount);
+            self.damage.superficial -= healed;
+            healed
+        };
+        Healing {
+            rouse_checks: vec![rouse],
+            healed,
+        }
+    }
+
+    /// Heal one point of aggravated Health damage, which takes three Rouse Checks.
+    /// Reaching Hunger 5 before the third check stops the healing.
+    pub fn heal_aggravated(&mut self, rng: &mut impl Rng) -> Healing {
+        let mut rouse_checks = Vec::new();
+        if self.damage.aggravated == 0 {
+            return Healing {
+                rouse_checks,
+                healed: 0,
+            };
+        }
+        for _ in 0..3 {
+            let rouse = self.rouse_check(None, rng);
+            let starving = rouse.outcome == RouseOutcome::Starving;
+            rouse_checks.push(rouse);
+            if starving {
+                return Healing {
+                    rouse_checks,
+                    healed: 0,
+                };
+            }
+        }
+        self.damage.aggravated -= 1;
+        Healing {
+            rouse_checks,
+            healed: 1,
+        }
+    }
+
+    /// At the end of a session, superficial Willpower damage recovers by the higher of
+    /// Composure and Resolve. Returns how much was recovered.
+    pub fn recover_willpower(&mut self) -> u8 {
+        let recovery = self.attributes[Attribute::Composure]
+            .value()
+            .max(self.attributes[Attribute::Resolve].value());
+        let recovered = self.willpower_damage.superficial.min(recovery);
+        self.willpower_damage.superficial -= recovered;
+        recovered
+    }
+
+    /// Health with the damage recorded on the sheet.
+    pub fn health(&self) -> Health {
+        Health::from_character(
+            self,
+            Some(self.damage.superficial),
+            Some(self.damage.aggravated),
+        )
+    }
+
+    /// Apply damage to the Health track. Superficial damage is halved, as it is for
+    /// vampires from most sources; use [Damage::take] on [Character::damage] directly
+    /// for damage that isn't.
+    pub fn take_damage(&mut self, kind: DamageKind, amount: u8) -> TrackState {
+        let amount = match kind {
+            DamageKind::Superficial => Damage::halved(amount),
+            DamageKind::Aggravated => amount,
+        };
+        let track = self.health().value;
+        self.damage.take(kind, amount, track);
+        self.health().state()
+    }
+
+    /// Apply damage to the Willpower track, e.g. from social conflict.
+    pub fn take_willpower_damage(&mut self, kind: DamageKind, amount: u8) -> TrackState {
+        let track = Willpower::from_character(self).value;
+        self.willpower_damage.take(kind, amount, track);
+        Willpower::from_character(self).state()
     }
 
     //TODO do we need this rather?
@@ -126,9 +451,29 @@ impl Character {
     }
 }
 
+/// The result of a roll to resist frenzy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrenzyTest {
+    pub pool: DicePool,
+    pub roll: Roll,
+    pub outcome: Outcome,
+    /// The compulsion the roll triggered, if any.
+    pub compulsion: Option<Compulsion>,
+}
+
+impl FrenzyTest {
+    pub fn resisted(&self) -> bool {
+        self.outcome.is_win()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::mock::StepRng;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use skills::{Skill, SkillRating};
     #[test]
     fn new_character_all_default_values() {
         let test_char = Character::builder()
@@ -143,14 +488,22 @@ mod tests {
                 player_name: String::from("Test Player"),
                 character_name: String::from("Test Character"),
                 chronicle: String::from("Test Chronicle by Night"),
+                clan: None,
+                predator_type: None,
                 attributes: Attributes::default(),
                 skills: Skills::default(),
+                disciplines: Disciplines::default(),
+                advantages: Default::default(),
+                experience: Default::default(),
+                blood_bonds: Default::default(),
                 damage: Damage::default(),
                 willpower_damage: Damage::default(),
                 humanity: Humanity::default(),
                 blood_potency: BloodPotency::default(),
                 generation: Default::default(),
                 hunger: Default::default(),
+                resonance: None,
+                compulsion_active: false,
             }
         );
     }
@@ -158,15 +511,15 @@ mod tests {
     #[test]
     fn attributes_passed_explicitly() {
         let attributes = Attributes {
-            strength: 5,
-            dexterity: 4,
-            stamina: 3,
-            charisma: 2,
-            manipulation: 2,
-            composure: 2,
-            intelligence: 1,
-            wits: 3,
-            resolve: 2,
+            strength: 5.into(),
+            dexterity: 4.into(),
+            stamina: 3.into(),
+            charisma: 2.into(),
+            manipulation: 2.into(),
+            composure: 2.into(),
+            intelligence: 1.into(),
+            wits: 3.into(),
+            resolve: 2.into(),
         };
         let test_char = Character::builder()
             .player_name(String::from("Test Player"))
@@ -179,24 +532,32 @@ mod tests {
             player_name: String::from("Test Player"),
             character_name: String::from("Test Character"),
             chronicle: String::from("Test Chronicle by Night"),
+            clan: None,
+            predator_type: None,
             attributes: Attributes {
-                strength: 5,
-                dexterity: 4,
-                stamina: 3,
-                charisma: 2,
-                manipulation: 2,
-                composure: 2,
-                intelligence: 1,
-                wits: 3,
-                resolve: 2,
+                strength: 5.into(),
+                dexterity: 4.into(),
+                stamina: 3.into(),
+                charisma: 2.into(),
+                manipulation: 2.into(),
+                composure: 2.into(),
+                intelligence: 1.into(),
+                wits: 3.into(),
+                resolve: 2.into(),
             },
             skills: Skills::default(),
+            disciplines: Disciplines::default(),
+            advantages: Default::default(),
+            experience: Default::default(),
+            blood_bonds: Default::default(),
             damage: Damage::default(),
             willpower_damage: Damage::default(),
             humanity: Humanity::default(),
             blood_potency: BloodPotency::default(),
             generation: Default::default(),
             hunger: Default::default(),
+            resonance: None,
+            compulsion_active: false,
         };
 
         assert_eq!(test_char, expected);
@@ -205,33 +566,33 @@ mod tests {
     #[test]
     fn skills_passed_explicitly_no_specialities() {
         let skills = Skills {
-            athletics: (1, None),
-            brawl: (2, None),
-            craft: (3, None),
-            drive: (4, None),
-            firearms: (5, None),
-            larceny: (0, None),
-            melee: (1, None),
-            stealth: (2, None),
-            survival: (3, None),
-            animal_ken: (4, None),
-            etiquette: (5, None),
-            insight: (0, None),
-            intimidation: (1, None),
-            leadership: (2, None),
-            performance: (3, None),
-            persuasion: (4, None),
-            streetwise: (5, None),
-            subterfuge: (0, None),
-            academics: (1, None),
-            awareness: (2, None),
-            finance: (3, None),
-            investigation: (4, None),
-            medicine: (5, None),
-            occult: (0, None),
-            politics: (1, None),
-            science: (2, None),
-            technology: (3, None),
+            athletics: SkillRating::new(1),
+            brawl: SkillRating::new(2),
+            craft: SkillRating::new(3),
+            drive: SkillRating::new(4),
+            firearms: SkillRating::new(5),
+            larceny: SkillRating::new(0),
+            melee: SkillRating::new(1),
+            stealth: SkillRating::new(2),
+            survival: SkillRating::new(3),
+            animal_ken: SkillRating::new(4),
+            etiquette: SkillRating::new(5),
+            insight: SkillRating::new(0),
+            intimidation: SkillRating::new(1),
+            leadership: SkillRating::new(2),
+            performance: SkillRating::new(3),
+            persuasion: SkillRating::new(4),
+            streetwise: SkillRating::new(5),
+            subterfuge: SkillRating::new(0),
+            academics: SkillRating::new(1),
+            awareness: SkillRating::new(2),
+            finance: SkillRating::new(3),
+            investigation: SkillRating::new(4),
+            medicine: SkillRating::new(5),
+            occult: SkillRating::new(0),
+            politics: SkillRating::new(1),
+            science: SkillRating::new(2),
+            technology: SkillRating::new(3),
         };
 
         let test_char = Character::builder()
@@ -245,44 +606,335 @@ mod tests {
             player_name: String::from(""),
             character_name: String::from(""),
             chronicle: String::from(""),
+            clan: None,
+            predator_type: None,
             attributes: Attributes::default(),
             skills: Skills {
-                athletics: (1, None),
-                brawl: (2, None),
-                craft: (3, None),
-                drive: (4, None),
-                firearms: (5, None),
-                larceny: (0, None),
-                melee: (1, None),
-                stealth: (2, None),
-                survival: (3, None),
-                animal_ken: (4, None),
-                etiquette: (5, None),
-                insight: (0, None),
-                intimidation: (1, None),
-                leadership: (2, None),
-                performance: (3, None),
-                persuasion: (4, None),
-                streetwise: (5, None),
-                subterfuge: (0, None),
-                academics: (1, None),
-                awareness: (2, None),
-                finance: (3, None),
-                investigation: (4, None),
-                medicine: (5, None),
-                occult: (0, None),
-                politics: (1, None),
-                science: (2, None),
-                technology: (3, None),
+                athletics: SkillRating::new(1),
+                brawl: SkillRating::new(2),
+                craft: SkillRating::new(3),
+                drive: SkillRating::new(4),
+                firearms: SkillRating::new(5),
+                larceny: SkillRating::new(0),
+                melee: SkillRating::new(1),
+                stealth: SkillRating::new(2),
+                survival: SkillRating::new(3),
+                animal_ken: SkillRating::new(4),
+                etiquette: SkillRating::new(5),
+                insight: SkillRating::new(0),
+                intimidation: SkillRating::new(1),
+                leadership: SkillRating::new(2),
+                performance: SkillRating::new(3),
+                persuasion: SkillRating::new(4),
+                streetwise: SkillRating::new(5),
+                subterfuge: SkillRating::new(0),
+                academics: SkillRating::new(1),
+                awareness: SkillRating::new(2),
+                finance: SkillRating::new(3),
+                investigation: SkillRating::new(4),
+                medicine: SkillRating::new(5),
+                occult: SkillRating::new(0),
+                politics: SkillRating::new(1),
+                science: SkillRating::new(2),
+                technology: SkillRating::new(3),
             },
+            disciplines: Default::default(),
+            advantages: Default::default(),
+            experience: Default::default(),
+            blood_bonds: Default::default(),
             damage: Default::default(),
             willpower_damage: Default::default(),
             humanity: Default::default(),
             hunger: Default::default(),
+            resonance: None,
+            compulsion_active: false,
             blood_potency: Default::default(),
             generation: Default::default(),
         };
 
         assert_eq!(test_char, expected);
     }
+
+    #[test]
+    fn superficial_health_damage_is_halved() {
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .build();
+        test_char.attributes.stamina = 2.into();
+
+        assert_eq!(
+            test_char.take_damage(DamageKind::Superficial, 5),
+            TrackState::Healthy
+        );
+        assert_eq!(test_char.damage.superficial, 3);
+        assert_eq!(
+            test_char.take_damage(DamageKind::Aggravated, 2),
+            TrackState::Impaired
+        );
+    }
+
+    #[test]
+    fn failed_rouse_check_raises_hunger() {
+        // always rolls a 1
+        let mut rng = StepRng::new(0, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .hunger(4.into())
+            .build();
+
+        let check = test_char.rouse_check(None, &mut rng);
+        assert_eq!(check.dice, vec![1]);
+        assert_eq!(check.outcome, RouseOutcome::HungerRose(5));
+        assert_eq!(test_char.hunger, 5);
+
+        assert_eq!(
+            test_char.rouse_check(None, &mut rng).outcome,
+            RouseOutcome::Starving
+        );
+        assert_eq!(test_char.hunger, 5);
+    }
+
+    #[test]
+    fn no_rousing_the_blood_at_hunger_5() {
+        // always rolls a 10
+        let mut rng = StepRng::new(0xF000_0000, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .hunger(5.into())
+            .build();
+
+        let check = test_char.rouse_check(Some(1), &mut rng);
+        assert!(check.dice.is_empty());
+        assert_eq!(check.outcome, RouseOutcome::Starving);
+    }
+
+    #[test]
+    fn blood_potency_allows_rouse_rerolls() {
+        let mut rng = StepRng::new(0, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .blood_potency(3.into())
+            .build();
+
+        assert_eq!(test_char.rouse_check(Some(2), &mut rng).dice.len(), 2);
+        assert_eq!(test_char.rouse_check(Some(3), &mut rng).dice.len(), 1);
+        assert_eq!(test_char.rouse_check(None, &mut rng).dice.len(), 1);
+    }
+
+    #[test]
+    fn seeded_rouse_checks_match_their_dice() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .build();
+
+        for _ in 0..5 {
+            let hunger = test_char.hunger.value();
+            let check = test_char.rouse_check(None, &mut rng);
+            if check.dice[0] >= 6 {
+                assert_eq!(check.outcome, RouseOutcome::Passed);
+                assert_eq!(test_char.hunger, hunger);
+            } else {
+                assert_eq!(check.outcome, RouseOutcome::HungerRose(hunger + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn feeding_slakes_hunger_and_keeps_resonance() {
+        use feeding::{Intensity, Resonance, Vessel};
+
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .hunger(4.into())
+            .build();
+        let resonance = ResonanceRecord {
+            resonance: Resonance::Melancholic,
+            intensity: Intensity::Acute,
+            dyscrasia: Some(String::from("Lost Love")),
+        };
+
+        let hunger = test_char.feed(Feeding {
+            vessel: Vessel::Human,
+            slake: 2,
+            killed: false,
+            resonance: Some(resonance.clone()),
+        });
+
+        assert_eq!(hunger, Ok(2));
+        assert_eq!(test_char.resonance, Some(resonance));
+    }
+
+    #[test]
+    fn bestial_failure_triggers_the_compulsion() {
+        // always rolls a 1
+        let mut rng = StepRng::new(0, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .clan(Clan::Brujah)
+            .hunger(2.into())
+            .build();
+        test_char.attributes.set_all_to_2();
+
+        let frenzy = test_char.resist_frenzy(3, &mut rng);
+        assert_eq!(frenzy.outcome, Outcome::BestialFailure);
+        assert!(!frenzy.resisted());
+        assert_eq!(frenzy.compulsion.map(|c| c.name), Some("Rebellion"));
+        assert!(test_char.compulsion_active);
+
+        // Strength 2 + Brawl 0 - Rebellion 2, but at least one die
+        let pool = DicePool::from_character(&test_char, Attribute::Strength, Skill::Brawl, None);
+        assert_eq!(pool.size(), 1);
+        // resisting frenzy is no action the compulsion gets in the way of
+        assert_eq!(DicePool::frenzy(&test_char).size(), 6);
+
+        test_char.resolve_compulsion();
+        let pool = DicePool::from_character(&test_char, Attribute::Strength, Skill::Brawl, None);
+        assert_eq!(pool.size(), 2);
+    }
+
+    #[test]
+    fn clans_without_compulsion_stay_calm() {
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .clan(Clan::Caitiff)
+            .build();
+
+        assert_eq!(test_char.trigger_compulsion(Outcome::MessyCritical), None);
+        assert!(!test_char.compulsion_active);
+    }
+
+    #[test]
+    fn mending_heals_superficial_damage_by_blood_potency() {
+        let mut rng = StepRng::new(0, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .blood_potency(2.into())
+            .hunger(4.into())
+            .build();
+        test_char.damage = Damage {
+            superficial: 3,
+            aggravated: 1,
+        };
+
+        // Blood Potency 2 mends 2 per check, even when the check fails
+        assert_eq!(test_char.mend(&mut rng).healed, 2);
+        assert_eq!(test_char.hunger, 5);
+        // but not at Hunger 5
+        assert_eq!(test_char.mend(&mut rng).healed, 0);
+        assert_eq!(test_char.heal_aggravated(&mut rng).rouse_checks.len(), 1);
+        assert_eq!(
+            test_char.damage,
+            Damage {
+                superficial: 1,
+                aggravated: 1
+            }
+        );
+    }
+
+    #[test]
+    fn starving_vampires_heal_nothing_even_on_a_passing_die() {
+        // always rolls a 10
+        let mut rng = StepRng::new(0xF000_0000, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .hunger(5.into())
+            .build();
+        test_char.damage = Damage {
+            superficial: 2,
+            aggravated: 1,
+        };
+
+        let healing = test_char.mend(&mut rng);
+        assert_eq!(healing.healed, 0);
+        assert_eq!(healing.rouse_checks[0].outcome, RouseOutcome::Starving);
+        let healing = test_char.heal_aggravated(&mut rng);
+        assert_eq!(healing.healed, 0);
+        assert_eq!(healing.rouse_checks.len(), 1);
+        assert_eq!(
+            test_char.damage,
+            Damage {
+                superficial: 2,
+                aggravated: 1
+            }
+        );
+    }
+
+    #[test]
+    fn nothing_to_mend_costs_no_rouse_check() {
+        let mut rng = StepRng::new(0, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .hunger(1.into())
+            .build();
+        test_char.damage.aggravated = 1;
+
+        let healing = test_char.mend(&mut rng);
+        assert!(healing.rouse_checks.is_empty());
+        assert_eq!(healing.healed, 0);
+        assert_eq!(test_char.hunger, 1);
+    }
+
+    #[test]
+    fn aggravated_damage_takes_three_rouse_checks() {
+        let mut rng = StepRng::new(0, 0);
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .build();
+        test_char.damage.aggravated = 2;
+
+        let healing = test_char.heal_aggravated(&mut rng);
+        assert_eq!(healing.rouse_checks.len(), 3);
+        assert_eq!(healing.healed, 1);
+        assert_eq!(test_char.damage.aggravated, 1);
+        assert_eq!(test_char.hunger, 3);
+    }
+
+    #[test]
+    fn willpower_recovers_by_composure_or_resolve() {
+        let mut test_char = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .build();
+        test_char.attributes.composure = 2.into();
+        test_char.attributes.resolve = 3.into();
+        test_char.willpower_damage = Damage {
+            superficial: 4,
+            aggravated: 1,
+        };
+
+        assert_eq!(test_char.recover_willpower(), 3);
+        assert_eq!(
+            test_char.willpower_damage,
+            Damage {
+                superficial: 1,
+                aggravated: 1
+            }
+        );
+    }
 }
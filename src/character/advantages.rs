@@ -0,0 +1,356 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A single merit, background or flaw, e.g. `Contacts ••• (criminals)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advantage {
+    pub name: String,
+    pub dots: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Advantages granted by e.g. the predator type don't count against the creation budget.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub granted: bool,
+}
+
+impl Advantage {
+    pub fn new(name: impl Into<String>, dots: u8, note: Option<String>) -> Self {
+        Self {
+            name: name.into(),
+            dots,
+            note,
+            granted: false,
+        }
+    }
+
+    /// Advantages have between one and five dots.
+    pub fn check_dots(&self) -> Result<(), DotsError> {
+        if !(1..=5).contains(&self.dots) {
+            return Err(DotsError(self.dots));
+        }
+        Ok(())
+    }
+}
+
+impl Display for Advantage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.dots)?;
+        if let Some(note) = &self.note {
+            write!(f, " ({note})")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdvantageKind {
+    Merit,
+    Background,
+    Flaw,
+}
+
+impl FromStr for AdvantageKind {
+    type Err = ParseAdvantageKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "m" | "merit" => AdvantageKind::Merit,
+            "b" | "background" => AdvantageKind::Background,
+            "f" | "flaw" => AdvantageKind::Flaw,
+            _ => return Err(ParseAdvantageKindError),
+        })
+    }
+}
+
+/// An error that occurs when an `AdvantageKind` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `AdvantageKind`.
+#[derive(Debug)]
+pub struct ParseAdvantageKindError;
+
+impl Display for ParseAdvantageKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match merit, background or flaw".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseAdvantageKindError {}
+
+/// Merits, backgrounds and flaws of a character; see Core Rulebook, p. 179ff.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Advantages {
+    #[serde(default)]
+    pub merits: Vec<Advantage>,
+    #[serde(default)]
+    pub backgrounds: Vec<Advantage>,
+    #[serde(default)]
+    pub flaws: Vec<Advantage>,
+}
+
+impl Advantages {
+    /// Add an advantage with between one and five dots.
+    pub fn add(&mut self, kind: AdvantageKind, advantage: Advantage) -> Result<(), DotsError> {
+        advantage.check_dots()?;
+        match kind {
+            AdvantageKind::Merit => self.merits.push(advantage),
+            AdvantageKind::Background => self.backgrounds.push(advantage),
+            AdvantageKind::Flaw => self.flaws.push(advantage),
+        }
+        Ok(())
+    }
+
+    /// Take over everything from `other`, e.g. the advantages granted by a predator type.
+    pub fn extend(&mut self, other: Advantages) {
+        self.merits.extend(other.merits);
+        self.backgrounds.extend(other.backgrounds);
+        self.flaws.extend(other.flaws);
+    }
+
+    /// Points spent on merits and backgrounds, not counting granted ones.
+    pub fn advantage_points(&self) -> u32 {
+        Self::points(self.merits.iter().chain(&self.backgrounds))
+    }
+
+    /// Points taken in flaws, not counting granted ones.
+    pub fn flaw_points(&self) -> u32 {
+        Self::points(self.flaws.iter())
+    }
+
+    fn points<'a>(advantages: impl Iterator<Item = &'a Advantage>) -> u32 {
+        advantages
+            .filter(|a| !a.granted)
+            .map(|a| u32::from(a.dots))
+            .sum()
+    }
+
+    /// Check that neither merits and backgrounds nor flaws exceed the creation budget.
+    /// Spending less than the budget is allowed; the remaining points are simply lost.
+    pub fn check_budget(&self, budget: &AdvantageBudget) -> Result<(), BudgetError> {
+        if self.advantage_points() > budget.advantages.into() {
+            return Err(BudgetError::Advantages {
+                spent: self.advantage_points(),
+                budget: budget.advantages,
+            });
+        }
+        if self.flaw_points() > budget.flaws.into() {
+            return Err(BudgetError::Flaws {
+                spent: self.flaw_points(),
+                budget: budget.flaws,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check that a new character stays within the budget and takes exactly the flaws
+    /// the budget requires.
+    pub fn check_creation(&self, budget: &AdvantageBudget) -> Result<(), BudgetError> {
+        self.check_budget(budget)?;
+        if self.flaw_points() < budget.flaws.into() {
+            return Err(BudgetError::MissingFlaws {
+                taken: self.flaw_points(),
+                required: budget.flaws,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.merits.is_empty() && self.backgrounds.is_empty() && self.flaws.is_empty()
+    }
+}
+
+/// How many points of advantages and flaws a new character gets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdvantageBudget {
+    pub advantages: u8,
+    pub flaws: u8,
+}
+
+impl Default for AdvantageBudget {
+    /// New characters get 7 points of advantages and 2 of flaws.
+    fn default() -> Self {
+        Self {
+            advantages: 7,
+            flaws: 2,
+        }
+    }
+}
+
+/// An error that occurs when more points are spent on advantages or flaws than the
+/// creation budget allows, or fewer flaws are taken than it requires.
+#[derive(Debug, PartialEq)]
+pub enum BudgetError {
+    Advantages { spent: u32, budget: u8 },
+    Flaws { spent: u32, budget: u8 },
+    MissingFlaws { taken: u32, required: u8 },
+}
+
+impl Display for BudgetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BudgetError::Advantages { spent, budget } => write!(
+                f,
+                "{spent} points spent on merits and backgrounds, but only {budget} are available"
+            ),
+            BudgetError::Flaws { spent, budget } => {
+                write!(
+                    f,
+                    "{spent} points of flaws taken, but only {budget} are allowed"
+                )
+            }
+            BudgetError::MissingFlaws { taken, required } => {
+                write!(
+                    f,
+                    "{taken} points of flaws taken, but {required} are required"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BudgetError {}
+
+/// An error that occurs when an advantage has no dots or more than five.
+#[derive(Debug, PartialEq)]
+pub struct DotsError(pub u8);
+
+impl Display for DotsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "an advantage has between 1 and 5 dots, {} is out of range",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for DotsError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_is_respected() {
+        let mut advantages = Advantages::default();
+        advantages
+            .add(
+                AdvantageKind::Background,
+                Advantage::new("Resources", 3, None),
+            )
+            .unwrap();
+        advantages
+            .add(
+                AdvantageKind::Merit,
+                Advantage::new("Linguistics", 1, Some(String::from("Italian"))),
+            )
+            .unwrap();
+        advantages
+            .add(AdvantageKind::Flaw, Advantage::new("Enemy", 2, None))
+            .unwrap();
+
+        assert_eq!(advantages.check_budget(&AdvantageBudget::default()), Ok(()));
+
+        advantages
+            .add(AdvantageKind::Background, Advantage::new("Haven", 4, None))
+            .unwrap();
+        assert_eq!(
+            advantages.check_budget(&AdvantageBudget::default()),
+            Err(BudgetError::Advantages {
+                spent: 8,
+                budget: 7
+            })
+        );
+    }
+
+    #[test]
+    fn new_characters_take_all_their_flaws() {
+        let mut advantages = Advantages::default();
+        advantages
+            .add(AdvantageKind::Flaw, Advantage::new("Enemy", 1, None))
+            .unwrap();
+        let budget = AdvantageBudget::default();
+
+        assert!(advantages.check_budget(&budget).is_ok());
+        assert_eq!(
+            advantages.check_creation(&budget),
+            Err(BudgetError::MissingFlaws {
+                taken: 1,
+                required: 2
+            })
+        );
+
+        advantages
+            .add(AdvantageKind::Flaw, Advantage::new("Illiterate", 1, None))
+            .unwrap();
+        assert_eq!(advantages.check_creation(&budget), Ok(()));
+    }
+
+    #[test]
+    fn granted_advantages_are_free() {
+        let mut advantages = Advantages::default();
+        advantages
+            .add(
+                AdvantageKind::Flaw,
+                Advantage {
+                    granted: true,
+                    ..Advantage::new("Prey Exclusion", 2, None)
+                },
+            )
+            .unwrap();
+        advantages
+            .add(AdvantageKind::Flaw, Advantage::new("Enemy", 2, None))
+            .unwrap();
+
+        assert_eq!(advantages.flaw_points(), 2);
+        assert!(advantages.check_budget(&AdvantageBudget::default()).is_ok());
+    }
+
+    #[test]
+    fn dots_must_be_between_one_and_five() {
+        let mut advantages = Advantages::default();
+
+        assert_eq!(
+            advantages.add(AdvantageKind::Merit, Advantage::new("Linguistics", 0, None)),
+            Err(DotsError(0))
+        );
+        assert_eq!(
+            advantages.add(AdvantageKind::Background, Advantage::new("Herd", 255, None)),
+            Err(DotsError(255))
+        );
+        assert!(advantages.is_empty());
+    }
+
+    #[test]
+    fn points_do_not_overflow() {
+        let mut advantages = Advantages::default();
+        for _ in 0..60 {
+            advantages
+                .add(AdvantageKind::Flaw, Advantage::new("Enemy", 5, None))
+                .unwrap();
+        }
+
+        assert_eq!(advantages.flaw_points(), 300);
+        assert_eq!(
+            advantages.check_budget(&AdvantageBudget::default()),
+            Err(BudgetError::Flaws {
+                spent: 300,
+                budget: 2
+            })
+        );
+    }
+
+    #[test]
+    fn advantage_kind_from_string() {
+        assert_eq!(
+            "b".parse::<AdvantageKind>().unwrap(),
+            AdvantageKind::Background
+        );
+        assert_eq!(
+            "Flaw".parse::<AdvantageKind>().unwrap(),
+            AdvantageKind::Flaw
+        );
+    }
+}
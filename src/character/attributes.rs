@@ -1,38 +1,34 @@
-use serde::{Deserialize, Serialize};
+use crate::character::rating::{next_value_at, Dots};
+use serde::de::{Error, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 use std::fmt::Display;
 use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
 /// Attributes that a character has.
-#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
 pub struct Attributes {
-    pub strength: u8,
-    pub dexterity: u8,
-    pub stamina: u8,
+    pub strength: Dots,
+    pub dexterity: Dots,
+    pub stamina: Dots,
 
-    pub charisma: u8,
-    pub manipulation: u8,
-    pub composure: u8,
+    pub charisma: Dots,
+    pub manipulation: Dots,
+    pub composure: Dots,
 
-    pub intelligence: u8,
-    pub wits: u8,
-    pub resolve: u8,
+    pub intelligence: Dots,
+    pub wits: Dots,
+    pub resolve: Dots,
 }
 
 impl Attributes {
     /// Set all attributes to 2 dots. We can use this with a mut instance
     /// to then later change values individually.
     pub fn set_all_to_2(&mut self) {
-        self.strength = 2;
-        self.dexterity = 2;
-        self.stamina = 2;
-        self.charisma = 2;
-        self.manipulation = 2;
-        self.composure = 2;
-        self.intelligence = 2;
-        self.wits = 2;
-        self.resolve = 2;
+        for attr in Attribute::ALL {
+            self[attr] = 2.into();
+        }
     }
 
     /// Set attributes during character creation.
@@ -43,14 +39,28 @@ impl Attributes {
         highest: Attribute,
         lowest: Attribute,
         three_threes: Vec<Attribute>,
-    ) {
+    ) -> Result<(), AttributeDistributionError> {
+        if three_threes.len() != 3 {
+            return Err(AttributeDistributionError::WrongThreeCount(
+                three_threes.len(),
+            ));
+        }
+        let mut picked = vec![highest];
+        for &attr in [lowest].iter().chain(&three_threes) {
+            if picked.contains(&attr) {
+                return Err(AttributeDistributionError::Duplicate(attr));
+            }
+            picked.push(attr);
+        }
+
         self.set_all_to_2();
-        self[highest] = 4;
-        self[lowest] = 1;
+        self[highest] = 4.into();
+        self[lowest] = 1.into();
 
         for attr in three_threes {
-            self[attr] = 3;
+            self[attr] = 3.into();
         }
+        Ok(())
     }
 }
 
@@ -58,7 +68,7 @@ impl Attributes {
 /// let attr: Attribute = Attribute::Stamina/* say you get this from user input ... */;
 /// character.attributes\[attr] += 1;
 impl Index<Attribute> for Attributes {
-    type Output = u8;
+    type Output = Dots;
 
     fn index(&self, attr: Attribute) -> &Self::Output {
         match attr {
@@ -75,6 +85,38 @@ impl Index<Attribute> for Attributes {
     }
 }
 
+/// Attributes are read field by field, so that an out-of-range value can be reported
+/// with the attribute it belongs to.
+impl<'de> Deserialize<'de> for Attributes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AttributesVisitor;
+
+        impl<'de> Visitor<'de> for AttributesVisitor {
+            type Value = Attributes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                "a map of attributes to their dots".fmt(f)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut attributes = Attributes::default();
+                let mut seen = Vec::new();
+                while let Some(attr) = map.next_key::<Attribute>()? {
+                    attributes[attr] =
+                        next_value_at(&mut map, format_args!("attributes.{}", attr.key()))?;
+                    seen.push(attr);
+                }
+                match Attribute::ALL.into_iter().find(|a| !seen.contains(a)) {
+                    Some(missing) => Err(A::Error::missing_field(missing.key())),
+                    None => Ok(attributes),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(AttributesVisitor)
+    }
+}
+
 // snip: from dmitrii @ Discord
 // fn is_attribute_at_least_threshold(&self, attribute: Attribute, threshold: u8) -> bool {
 //     self.attributes[attribute] >= threshold
@@ -96,7 +138,8 @@ impl IndexMut<Attribute> for Attributes {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Attribute {
     Strength,
     Dexterity,
@@ -109,6 +152,57 @@ pub enum Attribute {
     Resolve,
 }
 
+impl Attribute {
+    pub const ALL: [Attribute; 9] = [
+        Attribute::Strength,
+        Attribute::Dexterity,
+        Attribute::Stamina,
+        Attribute::Charisma,
+        Attribute::Manipulation,
+        Attribute::Composure,
+        Attribute::Intelligence,
+        Attribute::Wits,
+        Attribute::Resolve,
+    ];
+
+    /// The name of the attribute as used on the character sheet.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Attribute::Strength => "strength",
+            Attribute::Dexterity => "dexterity",
+            Attribute::Stamina => "stamina",
+            Attribute::Charisma => "charisma",
+            Attribute::Manipulation => "manipulation",
+            Attribute::Composure => "composure",
+            Attribute::Intelligence => "intelligence",
+            Attribute::Wits => "wits",
+            Attribute::Resolve => "resolve",
+        }
+    }
+
+    pub fn category(&self) -> AttributeCategory {
+        match self {
+            Attribute::Strength | Attribute::Dexterity | Attribute::Stamina => {
+                AttributeCategory::Physical
+            }
+            Attribute::Charisma | Attribute::Manipulation | Attribute::Composure => {
+                AttributeCategory::Social
+            }
+            Attribute::Intelligence | Attribute::Wits | Attribute::Resolve => {
+                AttributeCategory::Mental
+            }
+        }
+    }
+}
+
+/// Attributes are grouped by the columns of the character sheet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttributeCategory {
+    Physical,
+    Social,
+    Mental,
+}
+
 // /// Build an attribute with a value and its proper category.
 // ///
 // /// No attribute value can be greater than 5, in accordance with the rules of V5.
@@ -156,6 +250,28 @@ impl Display for ParseAttributeError {
 
 impl std::error::Error for ParseAttributeError {}
 
+/// An error that occurs when the attributes picked during creation don't follow the rules.
+#[derive(Debug, PartialEq)]
+pub enum AttributeDistributionError {
+    WrongThreeCount(usize),
+    Duplicate(Attribute),
+}
+
+impl Display for AttributeDistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AttributeDistributionError::WrongThreeCount(count) => {
+                write!(f, "expected 3 attributes with 3 dots, got {count}")
+            }
+            AttributeDistributionError::Duplicate(attr) => {
+                write!(f, "{attr:?} was picked more than once")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AttributeDistributionError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,18 +280,53 @@ mod tests {
     fn set_all_to_two_works() {
         let mut test_attributes = Attributes::default();
         let expected = Attributes {
-            strength: 2,
-            dexterity: 2,
-            stamina: 2,
-            charisma: 2,
-            manipulation: 2,
-            composure: 2,
-            intelligence: 2,
-            wits: 2,
-            resolve: 2,
+            strength: 2.into(),
+            dexterity: 2.into(),
+            stamina: 2.into(),
+            charisma: 2.into(),
+            manipulation: 2.into(),
+            composure: 2.into(),
+            intelligence: 2.into(),
+            wits: 2.into(),
+            resolve: 2.into(),
         };
 
         test_attributes.set_all_to_2();
         assert_eq!(test_attributes, expected);
     }
+
+    #[test]
+    fn attributes_are_picked_only_once() {
+        let mut attributes = Attributes::default();
+        let result = attributes.set_attributes_during_creation(
+            Attribute::Wits,
+            Attribute::Strength,
+            vec![Attribute::Charisma, Attribute::Wits, Attribute::Resolve],
+        );
+
+        assert_eq!(
+            result,
+            Err(AttributeDistributionError::Duplicate(Attribute::Wits))
+        );
+        assert_eq!(attributes, Attributes::default());
+    }
+
+    #[test]
+    fn attribute_out_of_range_names_the_field() {
+        let json = r#"{"strength": 9, "dexterity": 2, "stamina": 2, "charisma": 2, "manipulation": 2,
+            "composure": 2, "intelligence": 2, "wits": 2, "resolve": 2}"#;
+        let error = serde_json::from_str::<Attributes>(json).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("attributes.strength: 9 is out of range"));
+    }
+
+    #[test]
+    fn missing_attribute_is_rejected() {
+        let json = r#"{"strength": 2}"#;
+        let error = serde_json::from_str::<Attributes>(json).unwrap_err();
+
+        assert!(error.to_string().contains("missing field `dexterity`"));
+    }
 }
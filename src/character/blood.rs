@@ -1,8 +1,22 @@
-use serde::{Deserialize, Serialize};
+use crate::character::rating::Rating;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::fmt::Display;
 
-#[derive(PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+#[derive(PartialEq, PartialOrd, Debug, Default, Serialize)]
 pub struct Hunger(u8);
 
+/// Hunger from a sheet has to be between 0 and 5.
+impl<'de> Deserialize<'de> for Hunger {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        Rating::<5>::checked(value)
+            .map(|_| Self(value))
+            .map_err(|e| D::Error::custom(format!("hunger: {e}")))
+    }
+}
+
 impl From<u8> for Hunger {
     fn from(value: u8) -> Self {
         Self(value)
@@ -42,6 +56,15 @@ impl Hunger {
         Self(value)
     }
 
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
+    /// Raise Hunger by one, up to 5.
+    pub fn raise(&mut self) {
+        *self = Self::new(self.0.saturating_add(1));
+    }
+
     pub fn is_in_range(&self) -> bool {
         // we don't need to assert that it's 0 or more,
         // since unsigned integers are always non-negative
@@ -49,9 +72,19 @@ impl Hunger {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, PartialOrd, Debug)]
+#[derive(Serialize, PartialEq, PartialOrd, Debug)]
 pub struct BloodPotency(u8);
 
+/// Blood Potency from a sheet has to be between 0 and 10.
+impl<'de> Deserialize<'de> for BloodPotency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+        Rating::<10>::checked(value)
+            .map(|_| Self(value))
+            .map_err(|e| D::Error::custom(format!("blood_potency: {e}")))
+    }
+}
+
 impl Default for BloodPotency {
     /// Default BloodPotency should be one, not 0, because only Thin-Blood vampires have 0
     /// (which is possible to be and have, but it is not the default case).
@@ -67,6 +100,10 @@ impl From<u8> for BloodPotency {
 }
 
 impl BloodPotency {
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
     /// Automatically assign [BloodPotency] based on the provided [Generation].
     /// This is not intended as an absolute conversion table, as it is possible to buy
     /// higher values in [BloodPotency] with XP, and it is possible to change [Generation]
@@ -82,6 +119,128 @@ impl BloodPotency {
         }
         .into()
     }
+
+    /// Bane severity according to the Blood Potency table, Core Rulebook p. 216.
+    pub fn bane_severity(&self) -> u8 {
+        self.effects().bane_severity
+    }
+
+    /// The effects of this Blood Potency according to the table in the Core Rulebook, p. 216.
+    /// Anything above 10 is treated like 10.
+    pub fn effects(&self) -> BloodPotencyEffects {
+        use SlakeAmount::*;
+        let (blood_surge, mend_amount, power_bonus, rouse_reroll_level, bane_severity) =
+            match self.0 {
+                0 => (1, 1, 0, 0, 0),
+                1 => (2, 1, 0, 1, 2),
+                2 => (2, 2, 1, 1, 2),
+                3 => (3, 2, 1, 2, 3),
+                4 => (3, 3, 2, 2, 3),
+                5 => (4, 3, 2, 3, 4),
+                6 => (4, 3, 3, 3, 4),
+                7 => (5, 3, 3, 4, 5),
+                8 => (5, 4, 4, 4, 5),
+                9 => (6, 4, 4, 5, 6),
+                10.. => (6, 5, 5, 5, 6),
+            };
+        let (animal_and_bagged, human_slake_reduction, kill_to_slake_below) = match self.0 {
+            0..=1 => (Full, 0, None),
+            2 => (Half, 0, None),
+            3 => (Nothing, 0, None),
+            4 => (Nothing, 1, None),
+            5 => (Nothing, 1, Some(2)),
+            6..=7 => (Nothing, 2, Some(2)),
+            8..=9 => (Nothing, 2, Some(3)),
+            10.. => (Nothing, 3, Some(3)),
+        };
+
+        BloodPotencyEffects {
+            blood_surge,
+            mend_amount,
+            power_bonus,
+            rouse_reroll_level,
+            bane_severity,
+            feeding_penalty: FeedingPenalty {
+                animal_and_bagged,
+                human_slake_reduction,
+                kill_to_slake_below,
+            },
+        }
+    }
+}
+
+impl Display for BloodPotency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// What a level of [BloodPotency] means in play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BloodPotencyEffects {
+    /// Dice added to a roll by a Blood Surge.
+    pub blood_surge: u8,
+    /// Superficial damage mended per Rouse Check.
+    pub mend_amount: u8,
+    /// Dice added to discipline power rolls.
+    pub power_bonus: u8,
+    /// Failed Rouse Checks for powers up to this level may be re-rolled; 0 means none.
+    pub rouse_reroll_level: u8,
+    pub bane_severity: u8,
+    pub feeding_penalty: FeedingPenalty,
+}
+
+/// How well feeding slakes Hunger at a given [BloodPotency].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedingPenalty {
+    pub animal_and_bagged: SlakeAmount,
+    /// How much less Hunger feeding from a human slakes.
+    pub human_slake_reduction: u8,
+    /// Hunger can only be slaked below this by draining and killing a human.
+    pub kill_to_slake_below: Option<u8>,
+}
+
+/// How much of its usual amount a kind of blood still slakes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlakeAmount {
+    Full,
+    Half,
+    Nothing,
+}
+
+/// The result of a Rouse Check, see Core Rulebook, p. 211.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouseCheck {
+    /// The die rolled, and the re-roll if [BloodPotency] allowed one.
+    pub dice: Vec<u8>,
+    pub outcome: RouseOutcome,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RouseOutcome {
+    Passed,
+    /// Hunger rose to the given value.
+    HungerRose(u8),
+    /// The vampire is already at Hunger 5 and can't rouse the blood: they have to resist
+    /// a hunger frenzy, or fall into torpor if the check was to wake or heal.
+    Starving,
+}
+
+impl Display for RouseOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RouseOutcome::Passed => write!(f, "passed, Hunger stays the same"),
+            RouseOutcome::HungerRose(5) => write!(
+                f,
+                "failed, Hunger rose to 5: any further failed Rouse Check means hunger frenzy or torpor"
+            ),
+            RouseOutcome::HungerRose(hunger) => write!(f, "failed, Hunger rose to {hunger}"),
+            RouseOutcome::Starving => write!(
+                f,
+                "can't rouse the blood at Hunger 5: resist a hunger frenzy, or fall into torpor if you were waking or healing"
+            ),
+        }
+    }
 }
 
 /// Generation, a value between 1 and 16 (although higher values *are* allowed; it is possible
@@ -100,6 +259,24 @@ impl Default for Generation {
     }
 }
 
+impl Generation {
+    /// The highest Blood Potency a vampire of this generation can reach, according to
+    /// the table in the Core Rulebook, p. 216. Thin-blooded generations can't have any.
+    pub fn max_blood_potency(&self) -> u8 {
+        match self.0 {
+            ..=4 => 10,
+            5 => 9,
+            6 => 8,
+            7 => 7,
+            8 => 6,
+            9 => 5,
+            10..=11 => 4,
+            12..=13 => 3,
+            14.. => 0,
+        }
+    }
+}
+
 impl From<u8> for Generation {
     /// Get the generation from just a number above 0.
     ///
@@ -130,6 +307,35 @@ mod tests {
         assert!(!hunger2.is_in_range())
     }
 
+    #[test]
+    fn raising_hunger_stops_at_5() {
+        let mut hunger = Hunger::new(5);
+        hunger.raise();
+        assert_eq!(hunger, 5);
+
+        let mut hunger = Hunger::from(u8::MAX);
+        hunger.raise();
+        assert_eq!(hunger, 5);
+    }
+
+    #[test]
+    fn hunger_out_of_range_is_rejected_on_load() {
+        assert_eq!(
+            serde_json::from_str::<Hunger>("6").unwrap_err().to_string(),
+            "hunger: 6 is out of range, must be between 0 and 5"
+        );
+        assert_eq!(serde_json::from_str::<Hunger>("5").unwrap(), Hunger(5));
+    }
+
+    #[test]
+    fn blood_potency_out_of_range_is_rejected_on_load() {
+        assert!(serde_json::from_str::<BloodPotency>("11").is_err());
+        assert_eq!(
+            serde_json::from_str::<BloodPotency>("10").unwrap(),
+            BloodPotency(10)
+        );
+    }
+
     #[test]
     fn u8_into_blood_potency() {
         let bp: BloodPotency = 3.into();
@@ -157,6 +363,34 @@ mod tests {
         assert!(bp >= expected)
     }
 
+    #[test]
+    fn blood_potency_effects_table() {
+        let effects = BloodPotency(4).effects();
+
+        assert_eq!(effects.blood_surge, 3);
+        assert_eq!(effects.mend_amount, 3);
+        assert_eq!(effects.power_bonus, 2);
+        assert_eq!(effects.rouse_reroll_level, 2);
+        assert_eq!(effects.bane_severity, 3);
+        assert_eq!(
+            effects.feeding_penalty,
+            FeedingPenalty {
+                animal_and_bagged: SlakeAmount::Nothing,
+                human_slake_reduction: 1,
+                kill_to_slake_below: None,
+            }
+        );
+    }
+
+    #[test]
+    fn thin_blood_effects() {
+        let effects = BloodPotency(0).effects();
+
+        assert_eq!(effects.rouse_reroll_level, 0);
+        assert_eq!(effects.bane_severity, 0);
+        assert_eq!(effects.feeding_penalty.animal_and_bagged, SlakeAmount::Full);
+    }
+
     #[test]
     fn generation_0_turns_1() {
         let gen_0: Generation = 0.into();
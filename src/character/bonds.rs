@@ -0,0 +1,309 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar date in the chronicle, written as `YYYY-MM-DD` on the sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// A date that exists in the Gregorian calendar, so neither 2023-02-29 nor 2024-04-31.
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Self, ParseDateError> {
+        if !(1..=12).contains(&month) || !(1..=Self::days_in_month(year, month)).contains(&day) {
+            return Err(ParseDateError);
+        }
+        Ok(Self { year, month, day })
+    }
+
+    fn days_in_month(year: u16, month: u8) -> u8 {
+        match month {
+            4 | 6 | 9 | 11 => 30,
+            2 if year.is_multiple_of(4)
+                && (!year.is_multiple_of(100) || year.is_multiple_of(400)) =>
+            {
+                29
+            }
+            2 => 28,
+            _ => 31,
+        }
+    }
+
+    /// Today's date in UTC.
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() / 86_400)
+            .unwrap_or_default() as i64;
+        Self::from_days_since_epoch(days)
+    }
+
+    /// The date `days` days after 1970-01-01.
+    fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's `civil_from_days`
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as u16;
+
+        Self { year, month, day }
+    }
+
+    /// Whole months from `self` until `later`, or 0 if `later` isn't later.
+    pub fn months_until(&self, later: &Date) -> u32 {
+        let months = (later.year as i32 - self.year as i32) * 12
+            + (later.month as i32 - self.month as i32)
+            - i32::from(later.day < self.day);
+        months.max(0) as u32
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, '-');
+        let mut next = || parts.next().ok_or(ParseDateError);
+        let year = next()?.parse().map_err(|_| ParseDateError)?;
+        let month = next()?.parse().map_err(|_| ParseDateError)?;
+        let day = next()?.parse().map_err(|_| ParseDateError)?;
+        Self::new(year, month, day)
+    }
+}
+
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Date {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// An error that occurs when a `Date` should be parsed from a `String`
+/// that is not a valid `YYYY-MM-DD` date.
+#[derive(Debug, PartialEq)]
+pub struct ParseDateError;
+
+impl Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string is not a date in the form YYYY-MM-DD".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+/// A blood bond to the regnant a character drank from; see Core Rulebook, p. 234f.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BloodBond {
+    /// Name of the character the bond is to.
+    pub regnant: String,
+    /// Between 1 and 3; a bond of 3 is a full blood bond.
+    #[serde(deserialize_with = "strength")]
+    pub strength: u8,
+    pub last_drink: Date,
+}
+
+/// A bond's strength from a sheet has to be between 1 and 3.
+fn strength<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    let value = u8::deserialize(deserializer)?;
+    if !(1..=3).contains(&value) {
+        return Err(serde::de::Error::custom(format!(
+            "strength: {value} is out of range, must be between 1 and 3"
+        )));
+    }
+    Ok(value)
+}
+
+impl BloodBond {
+    /// The strength of the bond on `date`: a bond that isn't fed weakens by one level
+    /// for every month since the last drink.
+    pub fn strength_on(&self, date: &Date) -> u8 {
+        let decay = self.last_drink.months_until(date).min(3) as u8;
+        self.strength.saturating_sub(decay)
+    }
+}
+
+impl Display for BloodBond {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "bond to {} of strength {} (last drink {})",
+            self.regnant, self.strength, self.last_drink
+        )
+    }
+}
+
+/// Every blood bond a character has.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BloodBonds(Vec<BloodBond>);
+
+impl BloodBonds {
+    /// Drink from a regnant on `date`. Every drink on a new night strengthens the bond
+    /// by one, up to 3, after any decay since the last drink.
+    pub fn drink_from(&mut self, regnant: impl Into<String>, date: Date) -> &BloodBond {
+        let regnant = regnant.into();
+        let index = match self.0.iter().position(|b| b.regnant == regnant) {
+            Some(index) => index,
+            None => {
+                self.0.push(BloodBond {
+                    regnant,
+                    strength: 0,
+                    last_drink: date,
+                });
+                self.0.len() - 1
+            }
+        };
+
+        let bond = &mut self.0[index];
+        let strength = bond.strength_on(&date);
+        if strength == 0 || bond.last_drink != date {
+            bond.strength = (strength + 1).min(3);
+        }
+        bond.last_drink = date;
+        &self.0[index]
+    }
+
+    /// The bonds that are still in effect on `date`, with their decayed strength.
+    pub fn on(&self, date: &Date) -> Vec<BloodBond> {
+        self.0
+            .iter()
+            .filter_map(|bond| match bond.strength_on(date) {
+                0 => None,
+                strength => Some(BloodBond {
+                    strength,
+                    ..bond.clone()
+                }),
+            })
+            .collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &BloodBond> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> Date {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn dates_round_trip() {
+        assert_eq!(date("2024-03-09").to_string(), "2024-03-09");
+        assert_eq!("2024-13-01".parse::<Date>(), Err(ParseDateError));
+    }
+
+    #[test]
+    fn dates_must_exist() {
+        assert!(Date::new(2024, 2, 29).is_ok());
+        assert!(Date::new(2000, 2, 29).is_ok());
+        assert_eq!(Date::new(2023, 2, 29), Err(ParseDateError));
+        assert_eq!(Date::new(1900, 2, 29), Err(ParseDateError));
+        assert_eq!(Date::new(2024, 4, 31), Err(ParseDateError));
+        assert!(Date::new(2024, 12, 31).is_ok());
+    }
+
+    #[test]
+    fn dates_from_days_since_epoch() {
+        assert_eq!(Date::from_days_since_epoch(0), date("1970-01-01"));
+        assert_eq!(Date::from_days_since_epoch(19_782), date("2024-02-29"));
+        assert_eq!(Date::from_days_since_epoch(19_783), date("2024-03-01"));
+    }
+
+    #[test]
+    fn bond_strength_out_of_range_is_rejected_on_load() {
+        let bond = r#"{"regnant": "Prince Ruthven", "strength": 4, "last_drink": "2024-01-01"}"#;
+        assert_eq!(
+            serde_json::from_str::<BloodBond>(bond)
+                .unwrap_err()
+                .to_string(),
+            "strength: 4 is out of range, must be between 1 and 3 at line 1 column 43"
+        );
+        let bond = bond.replace("4", "3");
+        assert_eq!(
+            serde_json::from_str::<BloodBond>(&bond).unwrap().strength,
+            3
+        );
+    }
+
+    #[test]
+    fn whole_months_between_dates() {
+        assert_eq!(date("2024-01-15").months_until(&date("2024-02-14")), 0);
+        assert_eq!(date("2024-01-15").months_until(&date("2024-02-15")), 1);
+        assert_eq!(date("2023-11-30").months_until(&date("2024-03-01")), 3);
+        assert_eq!(date("2024-03-01").months_until(&date("2024-01-01")), 0);
+    }
+
+    #[test]
+    fn drinking_on_separate_nights_strengthens_the_bond() {
+        let mut bonds = BloodBonds::default();
+        bonds.drink_from("Prince Ruthven", date("2024-01-01"));
+        bonds.drink_from("Prince Ruthven", date("2024-01-01"));
+        assert_eq!(
+            bonds
+                .drink_from("Prince Ruthven", date("2024-01-08"))
+                .strength,
+            2
+        );
+        assert_eq!(
+            bonds
+                .drink_from("Prince Ruthven", date("2024-01-15"))
+                .strength,
+            3
+        );
+        assert_eq!(
+            bonds
+                .drink_from("Prince Ruthven", date("2024-01-16"))
+                .strength,
+            3
+        );
+    }
+
+    #[test]
+    fn unfed_bonds_decay() {
+        let mut bonds = BloodBonds::default();
+        bonds.drink_from("Prince Ruthven", date("2024-01-01"));
+        bonds.drink_from("Prince Ruthven", date("2024-01-02"));
+
+        assert_eq!(bonds.on(&date("2024-02-15"))[0].strength, 1);
+        assert!(bonds.on(&date("2024-03-02")).is_empty());
+
+        // drinking again after the bond lapsed starts over
+        assert_eq!(
+            bonds
+                .drink_from("Prince Ruthven", date("2024-06-01"))
+                .strength,
+            1
+        );
+    }
+}
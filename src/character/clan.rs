@@ -0,0 +1,360 @@
+use crate::character::blood::BloodPotency;
+use crate::character::disciplines::Discipline;
+use crate::character::Attribute;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The clans of V5, including the clanless Caitiff and the Thin-blooded.
+///
+/// See Core Rulebook, p. 65ff., and the Players Guide for Lasombra, Hecata, Ravnos,
+/// Salubri and Tzimisce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Clan {
+    #[serde(rename = "Banu Haqim")]
+    BanuHaqim,
+    Brujah,
+    Gangrel,
+    Hecata,
+    Lasombra,
+    Malkavian,
+    Ministry,
+    Nosferatu,
+    Ravnos,
+    Salubri,
+    Toreador,
+    Tremere,
+    Tzimisce,
+    Ventrue,
+    Caitiff,
+    #[serde(rename = "Thin-blood")]
+    ThinBlood,
+}
+
+/// The clan bane, with its [severity](BloodPotency::bane_severity) already worked into
+/// the description.
+#[derive(Debug, PartialEq)]
+pub struct Bane {
+    pub name: &'static str,
+    pub severity: u8,
+    pub description: String,
+}
+
+/// The clan compulsion that may be triggered by a messy critical or bestial failure.
+///
+/// `penalty` is the number of dice subtracted from the rolls in `affects` until the
+/// compulsion has been resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compulsion {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub penalty: u8,
+    pub affects: Affects,
+}
+
+/// The rolls that suffer the penalty of an active compulsion.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Affects {
+    /// Every action that doesn't pursue the compulsion.
+    AllActions,
+    /// Only rolls with one of these attributes.
+    Attributes(&'static [Attribute]),
+}
+
+impl Compulsion {
+    /// Whether a roll with `attribute` suffers the penalty.
+    pub fn affects(&self, attribute: Attribute) -> bool {
+        match self.affects {
+            Affects::AllActions => true,
+            Affects::Attributes(attributes) => attributes.contains(&attribute),
+        }
+    }
+}
+
+impl Clan {
+    pub const ALL: [Clan; 16] = [
+        Clan::BanuHaqim,
+        Clan::Brujah,
+        Clan::Gangrel,
+        Clan::Hecata,
+        Clan::Lasombra,
+        Clan::Malkavian,
+        Clan::Ministry,
+        Clan::Nosferatu,
+        Clan::Ravnos,
+        Clan::Salubri,
+        Clan::Toreador,
+        Clan::Tremere,
+        Clan::Tzimisce,
+        Clan::Ventrue,
+        Clan::Caitiff,
+        Clan::ThinBlood,
+    ];
+
+    /// The three in-clan disciplines.
+    /// Caitiff and Thin-bloods don't have any.
+    pub fn disciplines(&self) -> &'static [Discipline] {
+        use Discipline::*;
+        match self {
+            Clan::BanuHaqim => &[BloodSorcery, Celerity, Obfuscate],
+            Clan::Brujah => &[Celerity, Potence, Presence],
+            Clan::Gangrel => &[Animalism, Fortitude, Protean],
+            Clan::Hecata => &[Auspex, Fortitude, Oblivion],
+            Clan::Lasombra => &[Dominate, Oblivion, Potence],
+            Clan::Malkavian => &[Auspex, Dominate, Obfuscate],
+            Clan::Ministry => &[Obfuscate, Presence, Protean],
+            Clan::Nosferatu => &[Animalism, Obfuscate, Potence],
+            Clan::Ravnos => &[Animalism, Obfuscate, Presence],
+            Clan::Salubri => &[Auspex, Dominate, Fortitude],
+            Clan::Toreador => &[Auspex, Celerity, Presence],
+            Clan::Tremere => &[Auspex, BloodSorcery, Dominate],
+            Clan::Tzimisce => &[Animalism, Dominate, Protean],
+            Clan::Ventrue => &[Dominate, Fortitude, Presence],
+            Clan::Caitiff | Clan::ThinBlood => &[],
+        }
+    }
+
+    /// Whether a discipline is in-clan, i.e. cheaper to learn for members of this clan.
+    pub fn is_in_clan(&self, discipline: Discipline) -> bool {
+        self.disciplines().contains(&discipline)
+    }
+
+    /// The clan bane, scaled by the bane severity of the given [BloodPotency].
+    ///
+    /// Caitiff and Thin-bloods have no clan bane; their drawbacks are flaws instead.
+    pub fn bane(&self, blood_potency: &BloodPotency) -> Option<Bane> {
+        let severity = blood_potency.bane_severity();
+        let (name, description) = match self {
+            Clan::BanuHaqim => (
+                "Blood Addiction",
+                format!("When slaking at least one Hunger with the blood of another vampire, make a Hunger Frenzy test at difficulty {}.", 2 + severity),
+            ),
+            Clan::Brujah => (
+                "Violent Temper",
+                format!("Subtract {severity} dice from rolls to resist fury frenzy."),
+            ),
+            Clan::Gangrel => (
+                "Bestial Features",
+                format!("In frenzy, gain {severity} animal features, each reducing one Attribute by one dot for the night."),
+            ),
+            Clan::Hecata => (
+                "Painful Kiss",
+                format!("The Kiss causes agony instead of bliss; feeding from an unwilling, unrestrained vessel deals {severity} extra Aggravated damage to them."),
+            ),
+            Clan::Lasombra => (
+                "Distorted Image",
+                format!("Reflections and recordings are distorted; subtract {severity} dice from Technology rolls involving modern communication devices."),
+            ),
+            Clan::Malkavian => (
+                "Fractured Perspective",
+                format!("After a bestial failure or compulsion, suffer a {severity} dice penalty to one category of dice pools for the rest of the scene."),
+            ),
+            Clan::Ministry => (
+                "Abhors the Light",
+                format!("Subtract {severity} dice from all pools when exposed to direct bright light, and add {severity} Aggravated damage taken from sunlight."),
+            ),
+            Clan::Nosferatu => (
+                "Repulsiveness",
+                format!("Cannot pass as human; subtract {severity} dice from rolls to disguise themselves as mortal."),
+            ),
+            Clan::Ravnos => (
+                "Doomed",
+                format!("Daysleeping in the same place more than once in seven nights: roll {severity} dice and take Aggravated damage for each 10."),
+            ),
+            Clan::Salubri => (
+                "Hunted",
+                format!("Other vampires tasting their blood must resist a Hunger Frenzy at difficulty {}; their third eye weeps blood when using disciplines.", 2 + severity),
+            ),
+            Clan::Toreador => (
+                "Aesthetic Fixation",
+                format!("In drab or ugly surroundings, subtract {severity} dice from Discipline rolls."),
+            ),
+            Clan::Tremere => (
+                "Deficient Blood",
+                format!("Cannot blood bond other Kindred; mortals and ghouls need {severity} additional drinks to be bonded."),
+            ),
+            Clan::Tzimisce => (
+                "Grounded",
+                format!("When not resting among their chosen earth, take {severity} Aggravated Willpower damage upon waking."),
+            ),
+            Clan::Ventrue => (
+                "Rarefied Tastes",
+                format!("Feeding outside their preferred prey costs {severity} Willpower."),
+            ),
+            Clan::Caitiff | Clan::ThinBlood => return None,
+        };
+        Some(Bane {
+            name,
+            severity,
+            description,
+        })
+    }
+
+    /// The clan compulsion.
+    ///
+    /// Caitiff and Thin-bloods have no clan compulsion.
+    pub fn compulsion(&self) -> Option<Compulsion> {
+        let (name, description, penalty) = match self {
+            Clan::BanuHaqim => ("Judgment", "Punish anyone seen to transgress the vampire's personal convictions; other actions suffer the penalty.", 2),
+            Clan::Brujah => ("Rebellion", "Go against whatever or whoever represents the status quo; other actions suffer the penalty.", 2),
+            Clan::Gangrel => ("Feral Impulses", "Take on an animal's mindset; the penalty applies to Manipulation and Intelligence rolls and speech is limited to one word.", 3),
+            Clan::Hecata => ("Morbidity", "Predict a death or solve the cause of a local one; other actions suffer the penalty.", 2),
+            Clan::Lasombra => ("Ruthlessness", "After the next failed action, all pools suffer the penalty until a future attempt at the same goal succeeds.", 2),
+            Clan::Malkavian => ("Delusion", "Experience warped sensations; the penalty applies to Dexterity, Manipulation, Composure and Wits rolls.", 2),
+            Clan::Ministry => ("Transgression", "Cause someone to break a Chasm or Conviction; other actions suffer the penalty.", 2),
+            Clan::Nosferatu => ("Cryptophilia", "Learn a secret, at any cost; other actions suffer the penalty.", 2),
+            Clan::Ravnos => ("Tempting Fate", "Take the most daring course of action; actions taken in safety suffer the penalty.", 2),
+            Clan::Salubri => ("Affective Empathy", "Help someone whose suffering is apparent; other actions suffer the penalty.", 2),
+            Clan::Toreador => ("Obsession", "Become enraptured by something beautiful; other actions suffer the penalty.", 2),
+            Clan::Tremere => ("Perfectionism", "Anything less than perfection is failure; all pools suffer the penalty until a roll scores a critical.", 2),
+            Clan::Tzimisce => ("Covetousness", "Obsess over possessing something; other actions suffer the penalty.", 2),
+            Clan::Ventrue => ("Arrogance", "Someone must obey the vampire; other actions suffer the penalty.", 2),
+            Clan::Caitiff | Clan::ThinBlood => return None,
+        };
+        let affects = match self {
+            Clan::Gangrel => {
+                Affects::Attributes(&[Attribute::Manipulation, Attribute::Intelligence])
+            }
+            Clan::Malkavian => Affects::Attributes(&[
+                Attribute::Dexterity,
+                Attribute::Manipulation,
+                Attribute::Composure,
+                Attribute::Wits,
+            ]),
+            _ => Affects::AllActions,
+        };
+        Some(Compulsion {
+            name,
+            description,
+            penalty,
+            affects,
+        })
+    }
+}
+
+impl Display for Clan {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Clan::BanuHaqim => "Banu Haqim",
+            Clan::Brujah => "Brujah",
+            Clan::Gangrel => "Gangrel",
+            Clan::Hecata => "Hecata",
+            Clan::Lasombra => "Lasombra",
+            Clan::Malkavian => "Malkavian",
+            Clan::Ministry => "Ministry",
+            Clan::Nosferatu => "Nosferatu",
+            Clan::Ravnos => "Ravnos",
+            Clan::Salubri => "Salubri",
+            Clan::Toreador => "Toreador",
+            Clan::Tremere => "Tremere",
+            Clan::Tzimisce => "Tzimisce",
+            Clan::Ventrue => "Ventrue",
+            Clan::Caitiff => "Caitiff",
+            Clan::ThinBlood => "Thin-blood",
+        }
+        .fmt(f)
+    }
+}
+
+impl FromStr for Clan {
+    type Err = ParseClanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "banu haqim" => Clan::BanuHaqim,
+            "brujah" => Clan::Brujah,
+            "gangrel" => Clan::Gangrel,
+            "hecata" => Clan::Hecata,
+            "lasombra" => Clan::Lasombra,
+            "malkavian" => Clan::Malkavian,
+            "ministry" | "the ministry" => Clan::Ministry,
+            "nosferatu" => Clan::Nosferatu,
+            "ravnos" => Clan::Ravnos,
+            "salubri" => Clan::Salubri,
+            "toreador" => Clan::Toreador,
+            "tremere" => Clan::Tremere,
+            "tzimisce" => Clan::Tzimisce,
+            "ventrue" => Clan::Ventrue,
+            "caitiff" => Clan::Caitiff,
+            "thin-blood" | "thin blood" | "thinblood" => Clan::ThinBlood,
+            _ => return Err(ParseClanError),
+        })
+    }
+}
+
+/// An error that occurs when a `Clan` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `Clan`.
+#[derive(Debug)]
+pub struct ParseClanError;
+
+impl Display for ParseClanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match any clan".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseClanError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clan_from_string() {
+        assert_eq!("Banu Haqim".parse::<Clan>().unwrap(), Clan::BanuHaqim);
+        assert_eq!("thin-blood".parse::<Clan>().unwrap(), Clan::ThinBlood);
+    }
+
+    #[test]
+    #[should_panic]
+    fn non_existing_clan_from_string() {
+        let _ = "foo".parse::<Clan>().unwrap();
+    }
+
+    #[test]
+    fn toreador_in_clan_disciplines() {
+        assert_eq!(
+            Clan::Toreador.disciplines(),
+            &[
+                Discipline::Auspex,
+                Discipline::Celerity,
+                Discipline::Presence
+            ]
+        );
+    }
+
+    #[test]
+    fn bane_scales_with_blood_potency() {
+        let bane = Clan::Brujah.bane(&BloodPotency::from(3)).unwrap();
+        assert_eq!(bane.severity, 3);
+
+        let bane = Clan::Brujah.bane(&BloodPotency::from(1)).unwrap();
+        assert_eq!(bane.severity, 2);
+    }
+
+    #[test]
+    fn caitiff_has_no_bane_or_compulsion() {
+        assert!(Clan::Caitiff.bane(&BloodPotency::default()).is_none());
+        assert!(Clan::Caitiff.compulsion().is_none());
+        assert!(Clan::Caitiff.disciplines().is_empty());
+    }
+
+    #[test]
+    fn some_compulsions_only_affect_certain_attributes() {
+        let feral = Clan::Gangrel.compulsion().unwrap();
+        assert!(feral.affects(Attribute::Manipulation));
+        assert!(feral.affects(Attribute::Intelligence));
+        assert!(!feral.affects(Attribute::Strength));
+
+        let delusion = Clan::Malkavian.compulsion().unwrap();
+        assert!(delusion.affects(Attribute::Wits));
+        assert!(!delusion.affects(Attribute::Intelligence));
+
+        assert!(Clan::Brujah
+            .compulsion()
+            .unwrap()
+            .affects(Attribute::Strength));
+    }
+}
@@ -0,0 +1,375 @@
+use crate::character::rating::{Dots, RatingError};
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+
+/// The disciplines of V5; see Core Rulebook, p. 243ff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Discipline {
+    Animalism,
+    Auspex,
+    BloodSorcery,
+    Celerity,
+    Dominate,
+    Fortitude,
+    Obfuscate,
+    Oblivion,
+    Potence,
+    Presence,
+    Protean,
+    ThinBloodAlchemy,
+}
+
+impl Display for Discipline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Discipline::Animalism => "Animalism",
+            Discipline::Auspex => "Auspex",
+            Discipline::BloodSorcery => "Blood Sorcery",
+            Discipline::Celerity => "Celerity",
+            Discipline::Dominate => "Dominate",
+            Discipline::Fortitude => "Fortitude",
+            Discipline::Obfuscate => "Obfuscate",
+            Discipline::Oblivion => "Oblivion",
+            Discipline::Potence => "Potence",
+            Discipline::Presence => "Presence",
+            Discipline::Protean => "Protean",
+            Discipline::ThinBloodAlchemy => "Thin-blood Alchemy",
+        }
+        .fmt(f)
+    }
+}
+
+/// A learned discipline power.
+///
+/// Older sheets only list the names of the learned powers, so the level may be unknown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "PowerRepr")]
+pub struct Power {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<u8>,
+}
+
+impl Power {
+    pub fn new(name: impl Into<String>, level: u8) -> Self {
+        Self {
+            name: name.into(),
+            level: Some(level),
+        }
+    }
+}
+
+/// Powers are either written as `{"name": "Awe", "level": 1}` or just as `"Awe"`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PowerRepr {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default)]
+        level: Option<u8>,
+    },
+}
+
+impl From<PowerRepr> for Power {
+    fn from(repr: PowerRepr) -> Self {
+        match repr {
+            PowerRepr::Name(name) => Self { name, level: None },
+            PowerRepr::Full { name, level } => Self { name, level },
+        }
+    }
+}
+
+/// The dots a character has in a discipline, and the powers they learned with them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "DisciplineRatingRepr")]
+pub struct DisciplineRating {
+    pub dots: u8,
+    pub powers: Vec<Power>,
+}
+
+impl DisciplineRating {
+    /// Make sure no power has a higher level than the discipline has dots.
+    /// Powers with an unknown level can't be checked and are always accepted.
+    pub fn validate(&self) -> Result<(), PowerLevelError> {
+        match self
+            .powers
+            .iter()
+            .find(|p| p.level.is_some_and(|l| l > self.dots))
+        {
+            Some(power) => Err(PowerLevelError {
+                power: power.name.clone(),
+                level: power.level.unwrap_or_default(),
+                dots: self.dots,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A rating is either written as `{"dots": 2, "powers": [...]}`, or, in older sheets,
+/// as a plain list of powers with one dot per learned power.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DisciplineRatingRepr {
+    Rated {
+        dots: u8,
+        #[serde(default)]
+        powers: Vec<Power>,
+    },
+    Powers(Vec<Power>),
+}
+
+impl TryFrom<DisciplineRatingRepr> for DisciplineRating {
+    type Error = DisciplineRatingError;
+
+    fn try_from(repr: DisciplineRatingRepr) -> Result<Self, Self::Error> {
+        let rating = match repr {
+            DisciplineRatingRepr::Rated { dots, powers } => Self { dots, powers },
+            DisciplineRatingRepr::Powers(powers) => Self {
+                dots: u8::try_from(powers.len()).unwrap_or(u8::MAX),
+                powers,
+            },
+        };
+        Dots::checked(rating.dots).map_err(DisciplineRatingError::Dots)?;
+        rating
+            .validate()
+            .map_err(DisciplineRatingError::PowerLevel)?;
+        Ok(rating)
+    }
+}
+
+/// All disciplines a character has at least one dot in.
+///
+/// Serialized as a map from discipline to [DisciplineRating]; older sheets that have a list
+/// of single-entry maps (`[{"auspex": ["Sense the Unseen"]}, ...]`) can still be read.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Disciplines(BTreeMap<Discipline, DisciplineRating>);
+
+impl Disciplines {
+    pub fn get(&self, discipline: Discipline) -> Option<&DisciplineRating> {
+        self.0.get(&discipline)
+    }
+
+    /// The dots in a discipline, 0 if the character doesn't have it at all.
+    pub fn dots(&self, discipline: Discipline) -> u8 {
+        self.get(discipline).map_or(0, |r| r.dots)
+    }
+
+    /// Raise a discipline by one dot, adding it if the character didn't have it yet.
+    /// Like [Dots], a discipline can't go above 5.
+    pub fn add_dot(&mut self, discipline: Discipline) {
+        let rating = self.0.entry(discipline).or_default();
+        rating.dots = Dots::from(rating.dots.saturating_add(1)).value();
+    }
+
+    /// Learn a new power, as long as its level doesn't exceed the dots in the discipline.
+    pub fn learn_power(
+        &mut self,
+        discipline: Discipline,
+        power: Power,
+    ) -> Result<(), PowerLevelError> {
+        let dots = self.dots(discipline);
+        if let Some(level) = power.level.filter(|&l| l > dots) {
+            return Err(PowerLevelError {
+                power: power.name,
+                level,
+                dots,
+            });
+        }
+        self.0.entry(discipline).or_default().powers.push(power);
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Discipline, &DisciplineRating)> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(Discipline, DisciplineRating)> for Disciplines {
+    fn from_iter<T: IntoIterator<Item = (Discipline, DisciplineRating)>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<'de> Deserialize<'de> for Disciplines {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DisciplinesVisitor;
+
+        impl<'de> Visitor<'de> for DisciplinesVisitor {
+            type Value = Disciplines;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                "a map of disciplines or a list of single-discipline maps".fmt(f)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut disciplines = BTreeMap::new();
+                while let Some((discipline, rating)) = map.next_entry()? {
+                    insert_once(&mut disciplines, discipline, rating)?;
+                }
+                Ok(Disciplines(disciplines))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut disciplines = BTreeMap::new();
+                while let Some(entry) =
+                    seq.next_element::<BTreeMap<Discipline, DisciplineRating>>()?
+                {
+                    for (discipline, rating) in entry {
+                        insert_once(&mut disciplines, discipline, rating)?;
+                    }
+                }
+                Ok(Disciplines(disciplines))
+            }
+        }
+
+        /// A discipline that is listed twice would silently replace the first entry.
+        fn insert_once<E: Error>(
+            disciplines: &mut BTreeMap<Discipline, DisciplineRating>,
+            discipline: Discipline,
+            rating: DisciplineRating,
+        ) -> Result<(), E> {
+            if disciplines.insert(discipline, rating).is_some() {
+                return Err(E::custom(format!("{discipline} is listed more than once")));
+            }
+            Ok(())
+        }
+
+        deserializer.deserialize_any(DisciplinesVisitor)
+    }
+}
+
+/// An error that occurs when a power is learned (or read from a sheet)
+/// whose level is higher than the dots in its discipline.
+#[derive(Debug, PartialEq)]
+pub struct PowerLevelError {
+    pub power: String,
+    pub level: u8,
+    pub dots: u8,
+}
+
+impl Display for PowerLevelError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is a level {} power, but the discipline only has {} dots",
+            self.power, self.level, self.dots
+        )
+    }
+}
+
+impl std::error::Error for PowerLevelError {}
+
+/// An error that occurs when a discipline rating read from a sheet is invalid.
+#[derive(Debug, PartialEq)]
+pub enum DisciplineRatingError {
+    Dots(RatingError),
+    PowerLevel(PowerLevelError),
+}
+
+impl Display for DisciplineRatingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DisciplineRatingError::Dots(e) => write!(f, "dots: {e}"),
+            DisciplineRatingError::PowerLevel(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DisciplineRatingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_list_format_counts_one_dot_per_power() {
+        let json = r#"[{"auspex": ["Sense the Unseen"]}, {"presence": ["Awe", "Daunt", "Lingering Kiss"]}]"#;
+        let disciplines: Disciplines = serde_json::from_str(json).unwrap();
+
+        assert_eq!(disciplines.dots(Discipline::Auspex), 1);
+        assert_eq!(disciplines.dots(Discipline::Presence), 3);
+        assert_eq!(disciplines.dots(Discipline::Potence), 0);
+    }
+
+    #[test]
+    fn power_level_above_dots_is_rejected_on_load() {
+        let json =
+            r#"{"presence": {"dots": 1, "powers": [{"name": "Lingering Kiss", "level": 2}]}}"#;
+        let result = serde_json::from_str::<Disciplines>(json);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Lingering Kiss is a level 2 power"));
+    }
+
+    #[test]
+    fn disciplines_listed_twice_are_rejected() {
+        let json = r#"[{"auspex": ["Sense the Unseen"]}, {"auspex": ["Premonition"]}]"#;
+        let result = serde_json::from_str::<Disciplines>(json);
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Auspex is listed more than once"));
+    }
+
+    #[test]
+    fn dots_above_5_are_rejected() {
+        let result = serde_json::from_str::<Disciplines>(r#"{"auspex": {"dots": 9}}"#);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("dots: 9 is out of range"));
+
+        let mut disciplines = Disciplines::default();
+        for _ in 0..6 {
+            disciplines.add_dot(Discipline::Auspex);
+        }
+        assert_eq!(disciplines.dots(Discipline::Auspex), 5);
+    }
+
+    #[test]
+    fn learn_power_checks_dots() {
+        let mut disciplines = Disciplines::default();
+        disciplines.add_dot(Discipline::Presence);
+
+        assert!(disciplines
+            .learn_power(Discipline::Presence, Power::new("Awe", 1))
+            .is_ok());
+        assert_eq!(
+            disciplines.learn_power(Discipline::Presence, Power::new("Lingering Kiss", 2)),
+            Err(PowerLevelError {
+                power: String::from("Lingering Kiss"),
+                level: 2,
+                dots: 1
+            })
+        );
+    }
+
+    #[test]
+    fn disciplines_round_trip() {
+        let mut disciplines = Disciplines::default();
+        disciplines.add_dot(Discipline::BloodSorcery);
+        disciplines.add_dot(Discipline::BloodSorcery);
+        disciplines
+            .learn_power(Discipline::BloodSorcery, Power::new("Corrosive Vitae", 1))
+            .unwrap();
+
+        let json = serde_json::to_string(&disciplines).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Disciplines>(&json).unwrap(),
+            disciplines
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use crate::character::clan::Clan;
+use crate::character::disciplines::Discipline;
+use crate::character::skills::Skill;
+use crate::character::{Attribute, Character};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+
+/// Experience points a character earned and spent, kept as a ledger so that
+/// every purchase can be traced back.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Experience {
+    #[serde(default)]
+    pub ledger: Vec<LedgerEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LedgerEntry {
+    Earned { amount: u32, reason: String },
+    Spent { amount: u32, purchase: Purchase },
+}
+
+impl Experience {
+    pub fn earn(&mut self, amount: u32, reason: impl Into<String>) {
+        self.ledger.push(LedgerEntry::Earned {
+            amount,
+            reason: reason.into(),
+        });
+    }
+
+    pub fn earned(&self) -> u32 {
+        self.ledger
+            .iter()
+            .map(|e| match e {
+                LedgerEntry::Earned { amount, .. } => *amount,
+                LedgerEntry::Spent { .. } => 0,
+            })
+            .sum()
+    }
+
+    pub fn spent(&self) -> u32 {
+        self.ledger
+            .iter()
+            .map(|e| match e {
+                LedgerEntry::Spent { amount, .. } => *amount,
+                LedgerEntry::Earned { .. } => 0,
+            })
+            .sum()
+    }
+
+    pub fn available(&self) -> u32 {
+        self.earned().saturating_sub(self.spent())
+    }
+}
+
+/// Something a character can spend experience on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Purchase {
+    Attribute(Attribute),
+    Skill(Skill),
+    Specialty { skill: Skill, specialty: String },
+    Discipline(Discipline),
+    BloodPotency,
+}
+
+impl Purchase {
+    /// The cost of the purchase for this character, according to the table in the
+    /// Core Rulebook, p. 130:
+    ///
+    /// | Trait                   | Cost                 |
+    /// |-------------------------|----------------------|
+    /// | Attribute               | new level × 5        |
+    /// | Skill                   | new level × 3        |
+    /// | New skill               | 3                    |
+    /// | Specialty               | 3                    |
+    /// | In-clan discipline      | new level × 5        |
+    /// | Out-of-clan discipline  | new level × 7        |
+    /// | Caitiff discipline      | new level × 6        |
+    /// | Blood Potency           | new level × 10       |
+    ///
+    /// Blood Potency can't be raised above the limit of the character's generation, and
+    /// not at all by thin-bloods.
+    pub fn cost(&self, character: &Character) -> Result<u32, AdvanceError> {
+        let new_level = |current: u8, max: u8| {
+            if current >= max {
+                Err(AdvanceError::AtMaximum)
+            } else {
+                Ok(current as u32 + 1)
+            }
+        };
+
+        Ok(match self {
+            Purchase::Attribute(attr) => new_level(character.attributes[*attr].value(), 5)? * 5,
+            Purchase::Skill(skill) => match character.skills[*skill].dots.value() {
+                0 => 3,
+                dots => new_level(dots, 5)? * 3,
+            },
+            Purchase::Specialty { skill, specialty } => {
+                let rating = &character.skills[*skill];
+                if rating.dots == 0 {
+                    return Err(AdvanceError::NoDotsForSpecialty);
+                }
+                let specialty = specialty.trim();
+                if specialty.is_empty() {
+                    return Err(AdvanceError::EmptySpecialty);
+                }
+                if rating.has_specialty(specialty) {
+                    return Err(AdvanceError::DuplicateSpecialty);
+                }
+                3
+            }
+            Purchase::Discipline(discipline) => {
+                let multiplier = if character.clan == Some(Clan::Caitiff) {
+                    6
+                } else if character.is_in_clan(*discipline) {
+                    5
+                } else {
+                    7
+                };
+                new_level(character.disciplines.dots(*discipline), 5)? * multiplier
+            }
+            Purchase::BloodPotency => {
+                if character.clan == Some(Clan::ThinBlood) {
+                    return Err(AdvanceError::ThinBlood);
+                }
+                let max = character.generation.max_blood_potency();
+                new_level(character.blood_potency.value(), max)? * 10
+            }
+        })
+    }
+}
+
+impl Display for Purchase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Purchase::Attribute(attr) => write!(f, "{attr:?}"),
+            Purchase::Skill(skill) => write!(f, "{skill:?}"),
+            Purchase::Specialty { skill, specialty } => write!(f, "{skill:?} ({specialty})"),
+            Purchase::Discipline(discipline) => write!(f, "{discipline}"),
+            Purchase::BloodPotency => write!(f, "Blood Potency"),
+        }
+    }
+}
+
+/// An error that occurs when a character can't make a purchase.
+#[derive(Debug, PartialEq)]
+pub enum AdvanceError {
+    AtMaximum,
+    NoDotsForSpecialty,
+    EmptySpecialty,
+    DuplicateSpecialty,
+    ThinBlood,
+    NotEnoughXp { cost: u32, available: u32 },
+}
+
+impl Display for AdvanceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AdvanceError::AtMaximum => write!(f, "the trait is already at its maximum"),
+            AdvanceError::NoDotsForSpecialty => {
+                write!(f, "specialties can only be taken in skills with dots")
+            }
+            AdvanceError::EmptySpecialty => write!(f, "the specialty needs a name"),
+            AdvanceError::DuplicateSpecialty => write!(f, "the skill already has this specialty"),
+            AdvanceError::ThinBlood => write!(f, "thin-bloods can't raise their Blood Potency"),
+            AdvanceError::NotEnoughXp { cost, available } => {
+                write!(f, "costs {cost} XP, but only {available} XP are available")
+            }
+        }
+    }
+}
+
+impl std::error::Error for AdvanceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::skills::SkillRating;
+
+    fn test_character(clan: Clan, xp: u32) -> Character {
+        let mut character = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .clan(clan)
+            .build();
+        character.experience.earn(xp, "test session");
+        character
+    }
+
+    #[test]
+    fn discipline_costs_depend_on_clan() {
+        let toreador = test_character(Clan::Toreador, 0);
+        let caitiff = test_character(Clan::Caitiff, 0);
+
+        let presence = Purchase::Discipline(Discipline::Presence);
+        let potence = Purchase::Discipline(Discipline::Potence);
+
+        assert_eq!(presence.cost(&toreador), Ok(5));
+        assert_eq!(potence.cost(&toreador), Ok(7));
+        assert_eq!(presence.cost(&caitiff), Ok(6));
+    }
+
+    #[test]
+    fn new_skill_costs_three() {
+        let mut character = test_character(Clan::Brujah, 0);
+        assert_eq!(Purchase::Skill(Skill::Brawl).cost(&character), Ok(3));
+
+        character.skills.brawl = SkillRating::new(2);
+        assert_eq!(Purchase::Skill(Skill::Brawl).cost(&character), Ok(9));
+    }
+
+    #[test]
+    fn advance_records_purchase() {
+        let mut character = test_character(Clan::Brujah, 20);
+        character.attributes.strength = 2.into();
+
+        assert_eq!(
+            character.advance(Purchase::Attribute(Attribute::Strength)),
+            Ok(15)
+        );
+        assert_eq!(character.attributes.strength, 3);
+        assert_eq!(character.experience.spent(), 15);
+        assert_eq!(character.experience.available(), 5);
+    }
+
+    #[test]
+    fn advance_refuses_unaffordable_purchases() {
+        let mut character = test_character(Clan::Brujah, 5);
+
+        assert_eq!(
+            character.advance(Purchase::BloodPotency),
+            Err(AdvanceError::NotEnoughXp {
+                cost: 20,
+                available: 5
+            })
+        );
+        assert_eq!(character.blood_potency.value(), 1);
+        assert_eq!(character.experience.spent(), 0);
+    }
+
+    #[test]
+    fn specialty_needs_dots() {
+        let mut character = test_character(Clan::Brujah, 10);
+        let purchase = Purchase::Specialty {
+            skill: Skill::Academics,
+            specialty: String::from("Art History"),
+        };
+
+        assert_eq!(
+            character.advance(purchase.clone()),
+            Err(AdvanceError::NoDotsForSpecialty)
+        );
+
+        character.skills.academics = SkillRating::new(1);
+        assert_eq!(character.advance(purchase), Ok(3));
+        assert!(character.skills.academics.has_specialty("art history"));
+    }
+
+    #[test]
+    fn specialty_needs_a_name() {
+        let mut character = test_character(Clan::Brujah, 10);
+        character.skills.academics = SkillRating::new(1);
+
+        assert_eq!(
+            character.advance(Purchase::Specialty {
+                skill: Skill::Academics,
+                specialty: String::from("  "),
+            }),
+            Err(AdvanceError::EmptySpecialty)
+        );
+        assert!(character.skills.academics.specialties.is_empty());
+        assert_eq!(character.experience.spent(), 0);
+    }
+
+    #[test]
+    fn blood_potency_is_limited_by_generation() {
+        let mut character = test_character(Clan::Brujah, 100);
+        character.generation = 12.into();
+        character.blood_potency = 2.into();
+
+        assert_eq!(character.advance(Purchase::BloodPotency), Ok(30));
+        assert_eq!(
+            character.advance(Purchase::BloodPotency),
+            Err(AdvanceError::AtMaximum)
+        );
+        assert_eq!(character.blood_potency.value(), 3);
+    }
+
+    #[test]
+    fn thin_bloods_cant_buy_blood_potency() {
+        let mut character = test_character(Clan::ThinBlood, 100);
+        character.generation = 14.into();
+        character.blood_potency = 0.into();
+
+        assert_eq!(
+            character.advance(Purchase::BloodPotency),
+            Err(AdvanceError::ThinBlood)
+        );
+        assert_eq!(character.blood_potency.value(), 0);
+    }
+}
@@ -0,0 +1,188 @@
+use crate::character::blood::{FeedingPenalty, SlakeAmount};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+
+/// The emotional flavour of a vessel's blood, see Core Rulebook, p. 227ff.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resonance {
+    Choleric,
+    Melancholic,
+    Phlegmatic,
+    Sanguine,
+    Animal,
+    Empty,
+}
+
+impl Display for Resonance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Intensity {
+    Fleeting,
+    Intense,
+    /// Only acute resonance can come with a dyscrasia.
+    Acute,
+}
+
+/// The resonance of the last vessel a character fed from, as it is kept on the sheet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResonanceRecord {
+    pub resonance: Resonance,
+    pub intensity: Intensity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dyscrasia: Option<String>,
+}
+
+impl Display for ResonanceRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} {}", self.intensity, self.resonance)?;
+        if let Some(dyscrasia) = &self.dyscrasia {
+            write!(f, " (dyscrasia: {dyscrasia})")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Vessel {
+    Human,
+    Animal,
+    Bagged,
+}
+
+/// A single feeding: who was fed from, how much Hunger the feeding would slake before
+/// any Blood Potency penalties, and the vessel's resonance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feeding {
+    pub vessel: Vessel,
+    pub slake: u8,
+    /// Draining and killing a human is the only way to slake Hunger to 0.
+    pub killed: bool,
+    pub resonance: Option<ResonanceRecord>,
+}
+
+impl Feeding {
+    /// Hunger after this feeding, applying the feeding penalty of the character's
+    /// Blood Potency (Core Rulebook, p. 216):
+    ///
+    /// * animal and bagged blood slakes fully, half (rounded down) or nothing,
+    /// * human blood slakes less by the penalty's reduction,
+    /// * Hunger doesn't drop below 1, or below the penalty's threshold, without a kill.
+    pub fn hunger_after(&self, hunger: u8, penalty: &FeedingPenalty) -> u8 {
+        let slaked = match self.vessel {
+            Vessel::Human => self.slake.saturating_sub(penalty.human_slake_reduction),
+            Vessel::Animal | Vessel::Bagged => match penalty.animal_and_bagged {
+                SlakeAmount::Full => self.slake,
+                SlakeAmount::Half => self.slake / 2,
+                SlakeAmount::Nothing => 0,
+            },
+        };
+        let floor = if self.killed && self.vessel == Vessel::Human {
+            0
+        } else {
+            penalty.kill_to_slake_below.unwrap_or(1)
+        };
+
+        // feeding never raises Hunger, even if it is already below the floor
+        hunger.saturating_sub(slaked).max(floor.min(hunger))
+    }
+
+    pub fn validate(&self) -> Result<(), FeedingError> {
+        match &self.resonance {
+            Some(ResonanceRecord {
+                intensity: Intensity::Fleeting | Intensity::Intense,
+                dyscrasia: Some(_),
+                ..
+            }) => Err(FeedingError::DyscrasiaWithoutAcuteResonance),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An error that occurs when a feeding can't happen as described.
+#[derive(Debug, PartialEq)]
+pub enum FeedingError {
+    DyscrasiaWithoutAcuteResonance,
+}
+
+impl Display for FeedingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FeedingError::DyscrasiaWithoutAcuteResonance => {
+                write!(f, "only acute resonance can come with a dyscrasia")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FeedingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::blood::BloodPotency;
+
+    fn feeding(vessel: Vessel, slake: u8, killed: bool) -> Feeding {
+        Feeding {
+            vessel,
+            slake,
+            killed,
+            resonance: None,
+        }
+    }
+
+    #[test]
+    fn animal_blood_stops_slaking_at_high_blood_potency() {
+        let animal = feeding(Vessel::Animal, 2, false);
+
+        let low = BloodPotency::from(1).effects().feeding_penalty;
+        let mid = BloodPotency::from(2).effects().feeding_penalty;
+        let high = BloodPotency::from(3).effects().feeding_penalty;
+
+        assert_eq!(animal.hunger_after(4, &low), 2);
+        assert_eq!(animal.hunger_after(4, &mid), 3);
+        assert_eq!(animal.hunger_after(4, &high), 4);
+    }
+
+    #[test]
+    fn only_killing_slakes_below_the_floor() {
+        let penalty = BloodPotency::from(5).effects().feeding_penalty;
+
+        // human blood slakes one less at Blood Potency 5, and not below 2 without a kill
+        assert_eq!(
+            feeding(Vessel::Human, 3, false).hunger_after(5, &penalty),
+            3
+        );
+        assert_eq!(
+            feeding(Vessel::Human, 5, false).hunger_after(5, &penalty),
+            2
+        );
+        assert_eq!(feeding(Vessel::Human, 6, true).hunger_after(5, &penalty), 0);
+
+        let penalty = BloodPotency::from(1).effects().feeding_penalty;
+        assert_eq!(
+            feeding(Vessel::Human, 5, false).hunger_after(3, &penalty),
+            1
+        );
+    }
+
+    #[test]
+    fn dyscrasia_needs_acute_resonance() {
+        let mut feeding = feeding(Vessel::Human, 1, false);
+        feeding.resonance = Some(ResonanceRecord {
+            resonance: Resonance::Sanguine,
+            intensity: Intensity::Intense,
+            dyscrasia: Some(String::from("Smitten")),
+        });
+        assert_eq!(
+            feeding.validate(),
+            Err(FeedingError::DyscrasiaWithoutAcuteResonance)
+        );
+    }
+}
@@ -0,0 +1,550 @@
+use crate::character::advantages::{Advantage, Advantages};
+use crate::character::clan::Clan;
+use crate::character::disciplines::Discipline;
+use crate::character::skills::{Skill, Skills, SpecialtyError};
+use crate::character::{Attribute, Character};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// How a vampire prefers to hunt; see Core Rulebook, p. 175ff.
+///
+/// Picking a predator type during creation grants a specialty and a discipline dot
+/// (both chosen from a short list), advantages and flaws, and for some types a change
+/// to Humanity or Blood Potency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PredatorType {
+    Alleycat,
+    Bagger,
+    #[serde(rename = "Blood Leech")]
+    BloodLeech,
+    Cleaver,
+    Consensualist,
+    Farmer,
+    Osiris,
+    Sandman,
+    #[serde(rename = "Scene Queen")]
+    SceneQueen,
+    Siren,
+}
+
+impl PredatorType {
+    pub const ALL: [PredatorType; 10] = [
+        PredatorType::Alleycat,
+        PredatorType::Bagger,
+        PredatorType::BloodLeech,
+        PredatorType::Cleaver,
+        PredatorType::Consensualist,
+        PredatorType::Farmer,
+        PredatorType::Osiris,
+        PredatorType::Sandman,
+        PredatorType::SceneQueen,
+        PredatorType::Siren,
+    ];
+
+    /// The dice pool used for hunting.
+    /// Blood Leeches feed on other vampires and have no hunting pool.
+    pub fn hunting_pool(&self) -> Option<(Attribute, Skill)> {
+        Some(match self {
+            PredatorType::Alleycat => (Attribute::Strength, Skill::Brawl),
+            PredatorType::Bagger => (Attribute::Intelligence, Skill::Streetwise),
+            PredatorType::BloodLeech => return None,
+            PredatorType::Cleaver => (Attribute::Manipulation, Skill::Subterfuge),
+            PredatorType::Consensualist => (Attribute::Manipulation, Skill::Persuasion),
+            PredatorType::Farmer => (Attribute::Composure, Skill::AnimalKen),
+            PredatorType::Osiris => (Attribute::Manipulation, Skill::Subterfuge),
+            PredatorType::Sandman => (Attribute::Dexterity, Skill::Stealth),
+            PredatorType::SceneQueen => (Attribute::Manipulation, Skill::Persuasion),
+            PredatorType::Siren => (Attribute::Charisma, Skill::Subterfuge),
+        })
+    }
+
+    /// The specialties to choose one from.
+    pub fn specialties(&self) -> &'static [(Skill, OfferedSpecialty)] {
+        use OfferedSpecialty::*;
+        match self {
+            PredatorType::Alleycat => &[
+                (Skill::Intimidation, Named("Stickups")),
+                (Skill::Brawl, Named("Grappling")),
+            ],
+            PredatorType::Bagger => &[
+                (Skill::Larceny, Named("Lock Picking")),
+                (Skill::Streetwise, Named("Black Market")),
+            ],
+            PredatorType::BloodLeech => &[
+                (Skill::Brawl, Named("Kindred")),
+                (Skill::Stealth, Named("Against Kindred")),
+            ],
+            PredatorType::Cleaver => &[
+                (Skill::Persuasion, Named("Gaslighting")),
+                (Skill::Subterfuge, Named("Coverups")),
+            ],
+            PredatorType::Consensualist => &[
+                (Skill::Medicine, Named("Phlebotomy")),
+                (Skill::Persuasion, Named("Vessels")),
+            ],
+            PredatorType::Farmer => &[
+                (Skill::AnimalKen, Kind("Animal")),
+                (Skill::Survival, Named("Hunting")),
+            ],
+            PredatorType::Osiris => &[
+                (Skill::Occult, Kind("Tradition")),
+                (Skill::Performance, Kind("Entertainment Field")),
+            ],
+            PredatorType::Sandman => &[
+                (Skill::Medicine, Named("Anesthetics")),
+                (Skill::Stealth, Named("Break-in")),
+            ],
+            PredatorType::SceneQueen => &[
+                (Skill::Etiquette, Kind("Scene")),
+                (Skill::Leadership, Kind("Scene")),
+                (Skill::Streetwise, Kind("Scene")),
+            ],
+            PredatorType::Siren => &[
+                (Skill::Persuasion, Named("Seduction")),
+                (Skill::Subterfuge, Named("Seduction")),
+            ],
+        }
+    }
+
+    /// The disciplines to choose one dot from.
+    ///
+    /// Blood Sorcery is only on offer for clans that have it in-clan, and thin-bloods
+    /// get no discipline dot at all.
+    pub fn disciplines(&self, clan: Option<Clan>) -> Vec<Discipline> {
+        use Discipline::*;
+        if clan == Some(Clan::ThinBlood) {
+            return Vec::new();
+        }
+        let options: &[Discipline] = match self {
+            PredatorType::Alleycat => &[Celerity, Potence],
+            PredatorType::Bagger => &[BloodSorcery, Obfuscate],
+            PredatorType::BloodLeech => &[Celerity, Protean],
+            PredatorType::Cleaver => &[Dominate, Animalism],
+            PredatorType::Consensualist => &[Auspex, Fortitude],
+            PredatorType::Farmer => &[Animalism, Protean],
+            PredatorType::Osiris => &[BloodSorcery, Presence],
+            PredatorType::Sandman => &[Auspex, Obfuscate],
+            PredatorType::SceneQueen => &[Dominate, Potence],
+            PredatorType::Siren => &[Fortitude, Presence],
+        };
+        options
+            .iter()
+            .copied()
+            .filter(|&d| d != BloodSorcery || clan.is_some_and(|c| c.is_in_clan(d)))
+            .collect()
+    }
+
+    /// Merits, backgrounds and flaws granted by the predator type.
+    ///
+    /// Where the rules offer a choice between two flaws, the first one is granted
+    /// and the alternative mentioned in the note.
+    pub fn advantages(&self) -> Advantages {
+        let (merits, backgrounds, flaws) = match self {
+            PredatorType::Alleycat => (
+                vec![],
+                vec![granted("Contacts", 3, Some("criminals"))],
+                vec![],
+            ),
+            PredatorType::Bagger => (
+                vec![granted("Iron Gullet", 3, None)],
+                vec![],
+                vec![granted("Enemy", 2, Some("someone believes you owe them"))],
+            ),
+            PredatorType::BloodLeech => (
+                vec![],
+                vec![],
+                vec![
+                    granted(
+                        "Dark Secret",
+                        2,
+                        Some("Diablerist; or take Shunned instead"),
+                    ),
+                    granted("Prey Exclusion", 2, Some("mortals")),
+                ],
+            ),
+            PredatorType::Cleaver => (
+                vec![],
+                vec![granted("Herd", 2, None)],
+                vec![granted("Dark Secret", 1, Some("Cleaver"))],
+            ),
+            PredatorType::Consensualist => (
+                vec![],
+                vec![],
+                vec![
+                    granted("Dark Secret", 1, Some("Masquerade Breacher")),
+                    granted("Prey Exclusion", 1, Some("non-consenting")),
+                ],
+            ),
+            PredatorType::Farmer => (vec![], vec![], vec![granted("Farmer", 2, None)]),
+            PredatorType::Osiris => (
+                vec![],
+                vec![granted("Fame", 3, None)],
+                vec![granted("Enemies", 2, Some("or take Mythic flaws instead"))],
+            ),
+            PredatorType::Sandman => (vec![], vec![granted("Resources", 1, None)], vec![]),
+            PredatorType::SceneQueen => (
+                vec![],
+                vec![granted("Fame", 1, None), granted("Contacts", 1, None)],
+                vec![granted(
+                    "Disliked",
+                    1,
+                    Some("or take Prey Exclusion (other subculture) instead"),
+                )],
+            ),
+            PredatorType::Siren => (
+                vec![granted("Beautiful", 2, None)],
+                vec![],
+                vec![granted(
+                    "Enemy",
+                    1,
+                    Some("spurned lover or jealous partner"),
+                )],
+            ),
+        };
+        Advantages {
+            merits,
+            backgrounds,
+            flaws,
+        }
+    }
+
+    /// How much the predator type changes starting Humanity.
+    pub fn humanity_modifier(&self) -> i8 {
+        match self {
+            PredatorType::Alleycat | PredatorType::BloodLeech => -1,
+            PredatorType::Consensualist | PredatorType::Farmer => 1,
+            _ => 0,
+        }
+    }
+
+    /// How much the predator type raises starting Blood Potency.
+    pub fn blood_potency_modifier(&self) -> u8 {
+        match self {
+            PredatorType::BloodLeech => 1,
+            _ => 0,
+        }
+    }
+
+    /// Specialties of this predator type that can be taken with `skills`, i.e. those
+    /// in skills with at least one dot.
+    pub fn available_specialties(&self, skills: &Skills) -> Vec<(Skill, OfferedSpecialty)> {
+        self.specialties()
+            .iter()
+            .copied()
+            .filter(|&(skill, _)| skills[skill].dots.value() > 0)
+            .collect()
+    }
+
+    /// Apply the predator type to a character during creation.
+    ///
+    /// `specialty` and `discipline` are the player's picks; they have to be among
+    /// [PredatorType::specialties] and [PredatorType::disciplines] respectively. Where
+    /// only a [kind](OfferedSpecialty::Kind) of specialty is offered, the specialty is
+    /// the player's actual choice in that skill. Thin-bloods pick no discipline, and their
+    /// Blood Potency stays at 0.
+    pub fn apply(
+        &self,
+        character: &mut Character,
+        specialty: (Skill, &str),
+        discipline: Option<Discipline>,
+    ) -> Result<(), PredatorChoiceError> {
+        if !self
+            .specialties()
+            .iter()
+            .any(|&(skill, offered)| skill == specialty.0 && offered.accepts(specialty.1))
+        {
+            return Err(PredatorChoiceError::Specialty);
+        }
+        let disciplines = self.disciplines(character.clan);
+        match discipline {
+            Some(discipline) if disciplines.contains(&discipline) => {}
+            None if disciplines.is_empty() => {}
+            _ => return Err(PredatorChoiceError::Discipline),
+        }
+        character
+            .skills
+            .add_specialty(specialty.0, specialty.1)
+            .map_err(PredatorChoiceError::Skill)?;
+
+        character.predator_type = Some(*self);
+        if let Some(discipline) = discipline {
+            character.disciplines.add_dot(discipline);
+        }
+        character.advantages.extend(self.advantages());
+        character.humanity.value = character
+            .humanity
+            .value
+            .value()
+            .saturating_add_signed(self.humanity_modifier())
+            .into();
+        if character.clan != Some(Clan::ThinBlood) {
+            character.blood_potency =
+                (character.blood_potency.value() + self.blood_potency_modifier()).into();
+        }
+        Ok(())
+    }
+}
+
+/// A specialty offered by a predator type: either a named one, or only a kind of
+/// specialty, e.g. a specific animal, that the player names themselves, e.g. "Cats".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfferedSpecialty {
+    Named(&'static str),
+    Kind(&'static str),
+}
+
+impl OfferedSpecialty {
+    /// Whether `specialty` can be taken for this offer. A kind can't be taken as is.
+    pub fn accepts(&self, specialty: &str) -> bool {
+        match self {
+            OfferedSpecialty::Named(name) => *name == specialty,
+            OfferedSpecialty::Kind(_) => !specialty.eq_ignore_ascii_case(&self.to_string()),
+        }
+    }
+}
+
+impl Display for OfferedSpecialty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OfferedSpecialty::Named(name) => name.fmt(f),
+            OfferedSpecialty::Kind(kind) => write!(f, "Specific {kind}"),
+        }
+    }
+}
+
+/// An advantage that comes with the predator type and doesn't count against the budget.
+fn granted(name: &str, dots: u8, note: Option<&str>) -> Advantage {
+    Advantage {
+        granted: true,
+        ..Advantage::new(name, dots, note.map(String::from))
+    }
+}
+
+impl Display for PredatorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PredatorType::Alleycat => "Alleycat",
+            PredatorType::Bagger => "Bagger",
+            PredatorType::BloodLeech => "Blood Leech",
+            PredatorType::Cleaver => "Cleaver",
+            PredatorType::Consensualist => "Consensualist",
+            PredatorType::Farmer => "Farmer",
+            PredatorType::Osiris => "Osiris",
+            PredatorType::Sandman => "Sandman",
+            PredatorType::SceneQueen => "Scene Queen",
+            PredatorType::Siren => "Siren",
+        }
+        .fmt(f)
+    }
+}
+
+impl FromStr for PredatorType {
+    type Err = ParsePredatorTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "alleycat" => PredatorType::Alleycat,
+            "bagger" => PredatorType::Bagger,
+            "blood leech" => PredatorType::BloodLeech,
+            "cleaver" => PredatorType::Cleaver,
+            "consensualist" => PredatorType::Consensualist,
+            "farmer" => PredatorType::Farmer,
+            "osiris" => PredatorType::Osiris,
+            "sandman" => PredatorType::Sandman,
+            "scene queen" => PredatorType::SceneQueen,
+            "siren" => PredatorType::Siren,
+            _ => return Err(ParsePredatorTypeError),
+        })
+    }
+}
+
+/// An error that occurs when a `PredatorType` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `PredatorType`.
+#[derive(Debug)]
+pub struct ParsePredatorTypeError;
+
+impl Display for ParsePredatorTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match any predator type".fmt(f)
+    }
+}
+
+impl std::error::Error for ParsePredatorTypeError {}
+
+/// An error that occurs when a specialty or discipline is picked
+/// that the predator type doesn't offer, or the specialty can't be taken.
+#[derive(Debug, PartialEq)]
+pub enum PredatorChoiceError {
+    Specialty,
+    Discipline,
+    Skill(SpecialtyError),
+}
+
+impl Display for PredatorChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PredatorChoiceError::Specialty => {
+                "the predator type does not offer this specialty".fmt(f)
+            }
+            PredatorChoiceError::Discipline => {
+                "the predator type does not offer this discipline".fmt(f)
+            }
+            PredatorChoiceError::Skill(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for PredatorChoiceError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::skills::SkillRating;
+
+    fn test_character() -> Character {
+        Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .clan(Clan::Brujah)
+            .skills(Skills {
+                brawl: SkillRating::new(1),
+                animal_ken: SkillRating::new(2),
+                ..Default::default()
+            })
+            .build()
+    }
+
+    #[test]
+    fn predator_type_from_string() {
+        assert_eq!(
+            "Scene Queen".parse::<PredatorType>().unwrap(),
+            PredatorType::SceneQueen
+        );
+    }
+
+    #[test]
+    fn blood_sorcery_only_for_clans_that_have_it() {
+        assert_eq!(
+            PredatorType::Bagger.disciplines(Some(Clan::Brujah)),
+            vec![Discipline::Obfuscate]
+        );
+        assert_eq!(
+            PredatorType::Bagger.disciplines(Some(Clan::Tremere)),
+            vec![Discipline::BloodSorcery, Discipline::Obfuscate]
+        );
+    }
+
+    #[test]
+    fn blood_leech_applies_grants() {
+        let mut character = test_character();
+        PredatorType::BloodLeech
+            .apply(
+                &mut character,
+                (Skill::Brawl, "Kindred"),
+                Some(Discipline::Celerity),
+            )
+            .unwrap();
+
+        assert_eq!(character.predator_type, Some(PredatorType::BloodLeech));
+        assert!(character.skills[Skill::Brawl].has_specialty("Kindred"));
+        assert_eq!(character.disciplines.dots(Discipline::Celerity), 1);
+        assert_eq!(character.humanity.value, 6);
+        assert_eq!(character.blood_potency.value(), 2);
+        assert_eq!(character.advantages.flaws.len(), 2);
+        assert_eq!(character.advantages.flaw_points(), 0);
+    }
+
+    #[test]
+    fn choices_outside_the_predator_type_are_rejected() {
+        let mut character = test_character();
+
+        assert_eq!(
+            PredatorType::Siren.apply(
+                &mut character,
+                (Skill::Brawl, "Grappling"),
+                Some(Discipline::Presence)
+            ),
+            Err(PredatorChoiceError::Specialty)
+        );
+        assert_eq!(
+            PredatorType::Siren.apply(
+                &mut character,
+                (Skill::Persuasion, "Seduction"),
+                Some(Discipline::Potence)
+            ),
+            Err(PredatorChoiceError::Discipline)
+        );
+        assert_eq!(character.predator_type, None);
+    }
+
+    #[test]
+    fn thin_bloods_get_no_discipline_or_blood_potency() {
+        let mut character = test_character();
+        character.clan = Some(Clan::ThinBlood);
+        character.blood_potency = 0.into();
+
+        assert!(PredatorType::BloodLeech
+            .disciplines(character.clan)
+            .is_empty());
+        assert_eq!(
+            PredatorType::BloodLeech.apply(
+                &mut character,
+                (Skill::Brawl, "Kindred"),
+                Some(Discipline::Celerity)
+            ),
+            Err(PredatorChoiceError::Discipline)
+        );
+        PredatorType::BloodLeech
+            .apply(&mut character, (Skill::Brawl, "Kindred"), None)
+            .unwrap();
+        assert!(character.disciplines.is_empty());
+        assert_eq!(character.blood_potency.value(), 0);
+    }
+
+    #[test]
+    fn specialties_need_dots_in_the_skill() {
+        let mut character = test_character();
+
+        assert_eq!(
+            PredatorType::Farmer.available_specialties(&character.skills),
+            vec![(Skill::AnimalKen, OfferedSpecialty::Kind("Animal"))]
+        );
+        assert_eq!(
+            PredatorType::Farmer.apply(
+                &mut character,
+                (Skill::Survival, "Hunting"),
+                Some(Discipline::Animalism)
+            ),
+            Err(PredatorChoiceError::Skill(SpecialtyError::NoDots(
+                Skill::Survival
+            )))
+        );
+        assert_eq!(character.disciplines.dots(Discipline::Animalism), 0);
+    }
+
+    #[test]
+    fn specialty_kinds_are_named_by_the_player() {
+        let mut character = test_character();
+
+        assert_eq!(
+            PredatorType::Farmer.apply(
+                &mut character,
+                (Skill::AnimalKen, "Specific Animal"),
+                Some(Discipline::Animalism)
+            ),
+            Err(PredatorChoiceError::Specialty)
+        );
+        PredatorType::Farmer
+            .apply(
+                &mut character,
+                (Skill::AnimalKen, "Cats"),
+                Some(Discipline::Animalism),
+            )
+            .unwrap();
+        assert_eq!(character.skills.animal_ken.specialties, vec!["Cats"]);
+    }
+}
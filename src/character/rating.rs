@@ -0,0 +1,108 @@
+use serde::de::{Error, MapAccess};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::fmt::Display;
+
+/// A trait value between 0 and `MAX`, e.g. [Dots] for attributes and skills.
+///
+/// Sheets with values out of range are rejected when they are read; use
+/// [Rating::checked] to get the same check in code. `From<u8>` instead caps the value
+/// at `MAX`, which is handy for values that are known to be fine.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Rating<const MAX: u8>(u8);
+
+/// Dots in an attribute or skill, between 0 and 5.
+pub type Dots = Rating<5>;
+
+impl<const MAX: u8> Rating<MAX> {
+    pub fn checked(value: u8) -> Result<Self, RatingError> {
+        if value > MAX {
+            return Err(RatingError { value, max: MAX });
+        }
+        Ok(Self(value))
+    }
+
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<const MAX: u8> From<u8> for Rating<MAX> {
+    fn from(value: u8) -> Self {
+        Self(value.min(MAX))
+    }
+}
+
+impl<const MAX: u8> PartialEq<u8> for Rating<MAX> {
+    fn eq(&self, other: &u8) -> bool {
+        &self.0 == other
+    }
+}
+
+impl<const MAX: u8> Display for Rating<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<const MAX: u8> Serialize for Rating<MAX> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de, const MAX: u8> Deserialize<'de> for Rating<MAX> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::checked(u8::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// An error that occurs when a trait value is out of its allowed range.
+#[derive(Debug, PartialEq)]
+pub struct RatingError {
+    pub value: u8,
+    pub max: u8,
+}
+
+impl Display for RatingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} is out of range, must be between 0 and {}",
+            self.value, self.max
+        )
+    }
+}
+
+impl std::error::Error for RatingError {}
+
+/// Read the value of the current map entry, naming `path` in the error if that fails,
+/// so that e.g. a Strength of 9 is reported as `attributes.strength: 9 is out of range`.
+pub(crate) fn next_value_at<'de, A, T>(map: &mut A, path: fmt::Arguments) -> Result<T, A::Error>
+where
+    A: MapAccess<'de>,
+    T: Deserialize<'de>,
+{
+    map.next_value()
+        .map_err(|e| A::Error::custom(format!("{path}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dots_out_of_range_are_rejected() {
+        assert!(serde_json::from_str::<Dots>("5").is_ok());
+        assert_eq!(
+            serde_json::from_str::<Dots>("9").unwrap_err().to_string(),
+            "9 is out of range, must be between 0 and 5"
+        );
+    }
+
+    #[test]
+    fn from_u8_caps_at_max() {
+        assert_eq!(Dots::from(7), 5);
+        assert_eq!(Rating::<10>::from(7), 7);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::character::advantages::AdvantageBudget;
+use crate::character::blood::{BloodPotency, Generation};
+use crate::character::clan::Clan;
+use crate::character::stats::Humanity;
+use crate::character::Character;
+use std::fmt;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// How long ago a new character was Embraced, see Core Rulebook, p. 137f.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SeaOfTime {
+    /// Embraced within the last 15 years.
+    Childe,
+    /// Embraced between 1940 and about a decade ago.
+    Neonate,
+    /// Embraced between 1780 and 1940.
+    Ancilla,
+}
+
+impl SeaOfTime {
+    pub const ALL: [SeaOfTime; 3] = [SeaOfTime::Childe, SeaOfTime::Neonate, SeaOfTime::Ancilla];
+
+    /// The generations a character of this age can be; childer are thin-blooded.
+    pub fn generations(&self) -> RangeInclusive<u8> {
+        match self {
+            SeaOfTime::Childe => 14..=16,
+            SeaOfTime::Neonate => 12..=13,
+            SeaOfTime::Ancilla => 10..=11,
+        }
+    }
+
+    /// Experience points to spend on top of the usual creation process.
+    pub fn experience(&self) -> u32 {
+        match self {
+            SeaOfTime::Childe => 0,
+            SeaOfTime::Neonate => 15,
+            SeaOfTime::Ancilla => 35,
+        }
+    }
+
+    /// Ancillae get two more points of advantages, and have to take two more of flaws.
+    pub fn advantage_budget(&self) -> AdvantageBudget {
+        let budget = AdvantageBudget::default();
+        match self {
+            SeaOfTime::Childe | SeaOfTime::Neonate => budget,
+            SeaOfTime::Ancilla => AdvantageBudget {
+                advantages: budget.advantages + 2,
+                flaws: budget.flaws + 2,
+            },
+        }
+    }
+
+    pub fn humanity(&self) -> Humanity {
+        match self {
+            SeaOfTime::Childe | SeaOfTime::Neonate => Humanity::default(),
+            SeaOfTime::Ancilla => Humanity::default().new_for_ancilla(),
+        }
+    }
+
+    /// Set generation, Blood Potency, Humanity and starting experience of a new character.
+    ///
+    /// Thin-bloods are always childer, and childer always thin-blooded.
+    pub fn apply(&self, character: &mut Character, generation: u8) -> Result<(), SeaOfTimeError> {
+        if (character.clan == Some(Clan::ThinBlood)) != (*self == SeaOfTime::Childe) {
+            return Err(SeaOfTimeError::ThinBlood(*self));
+        }
+        if !self.generations().contains(&generation) {
+            return Err(SeaOfTimeError::Generation {
+                sea_of_time: *self,
+                generation,
+            });
+        }
+        let generation = Generation::from(generation);
+        character.blood_potency = BloodPotency::from_generation(&generation);
+        character.generation = generation;
+        character.humanity = self.humanity();
+        if self.experience() > 0 {
+            character
+                .experience
+                .earn(self.experience(), format!("starting experience as {self}"));
+        }
+        Ok(())
+    }
+}
+
+impl Display for SeaOfTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeaOfTime::Childe => "childe",
+            SeaOfTime::Neonate => "neonate",
+            SeaOfTime::Ancilla => "ancilla",
+        }
+        .fmt(f)
+    }
+}
+
+impl FromStr for SeaOfTime {
+    type Err = ParseSeaOfTimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "childe" => SeaOfTime::Childe,
+            "neonate" => SeaOfTime::Neonate,
+            "ancilla" => SeaOfTime::Ancilla,
+            _ => return Err(ParseSeaOfTimeError),
+        })
+    }
+}
+
+/// An error that occurs when a `SeaOfTime` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `SeaOfTime`.
+#[derive(Debug)]
+pub struct ParseSeaOfTimeError;
+
+impl Display for ParseSeaOfTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match childe, neonate or ancilla".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseSeaOfTimeError {}
+
+/// An error that occurs when the generation or the clan doesn't fit the character's age.
+#[derive(Debug, PartialEq)]
+pub enum SeaOfTimeError {
+    Generation {
+        sea_of_time: SeaOfTime,
+        generation: u8,
+    },
+    /// Only thin-bloods are childer, and they can't be anything else.
+    ThinBlood(SeaOfTime),
+}
+
+impl Display for SeaOfTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeaOfTimeError::Generation {
+                sea_of_time,
+                generation,
+            } => {
+                let allowed = sea_of_time.generations();
+                write!(
+                    f,
+                    "generation {generation} doesn't fit a character who is {sea_of_time}, it must be between {} and {}",
+                    allowed.start(),
+                    allowed.end()
+                )
+            }
+            SeaOfTimeError::ThinBlood(SeaOfTime::Childe) => {
+                write!(f, "a character who is childe has to be thin-blooded")
+            }
+            SeaOfTimeError::ThinBlood(sea_of_time) => {
+                write!(
+                    f,
+                    "a thin-blooded character has to be childe, not {sea_of_time}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeaOfTimeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character() -> Character {
+        Character::builder()
+            .player_name(String::from("Jane"))
+            .character_name(String::from("Alexandra"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .build()
+    }
+
+    #[test]
+    fn ancillae_are_older_and_more_experienced() {
+        let mut character = character();
+        SeaOfTime::Ancilla.apply(&mut character, 10).unwrap();
+
+        assert_eq!(character.generation, 10.into());
+        assert_eq!(character.blood_potency, 2.into());
+        assert_eq!(character.humanity.value.value(), 6);
+        assert_eq!(character.experience.available(), 35);
+        assert_eq!(
+            SeaOfTime::Ancilla.advantage_budget(),
+            AdvantageBudget {
+                advantages: 9,
+                flaws: 4
+            }
+        );
+    }
+
+    #[test]
+    fn generation_must_fit_the_age() {
+        let mut character = character();
+
+        assert_eq!(
+            SeaOfTime::Neonate.apply(&mut character, 10),
+            Err(SeaOfTimeError::Generation {
+                sea_of_time: SeaOfTime::Neonate,
+                generation: 10
+            })
+        );
+
+        character.clan = Some(Clan::ThinBlood);
+        SeaOfTime::Childe.apply(&mut character, 14).unwrap();
+        assert_eq!(character.blood_potency, 0.into());
+        assert_eq!(character.experience.available(), 0);
+    }
+
+    #[test]
+    fn childer_and_only_childer_are_thin_blooded() {
+        let mut character = character();
+        character.clan = Some(Clan::Brujah);
+        assert_eq!(
+            SeaOfTime::Childe.apply(&mut character, 14),
+            Err(SeaOfTimeError::ThinBlood(SeaOfTime::Childe))
+        );
+
+        character.clan = Some(Clan::ThinBlood);
+        assert_eq!(
+            SeaOfTime::Neonate
+                .apply(&mut character, 12)
+                .unwrap_err()
+                .to_string(),
+            "a thin-blooded character has to be childe, not neonate"
+        );
+    }
+}
@@ -1,45 +1,196 @@
-use serde::{Deserialize, Serialize};
+use crate::character::rating::{next_value_at, Dots, RatingError};
+use serde::de::{Error, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::fmt;
 use std::fmt::Display;
-use std::ops::Index;
+use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
-#[derive(Deserialize, Serialize, Debug, Default, PartialEq)]
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
 pub struct Skills {
-    pub athletics: (u8, Option<String>),
-    pub brawl: (u8, Option<String>),
-    pub craft: (u8, Option<String>),
-    pub drive: (u8, Option<String>),
-    pub firearms: (u8, Option<String>),
-    pub larceny: (u8, Option<String>),
-    pub melee: (u8, Option<String>),
-    pub stealth: (u8, Option<String>),
-    pub survival: (u8, Option<String>),
-    pub animal_ken: (u8, Option<String>),
-    pub etiquette: (u8, Option<String>),
-    pub insight: (u8, Option<String>),
-    pub intimidation: (u8, Option<String>),
-    pub leadership: (u8, Option<String>),
-    pub performance: (u8, Option<String>),
-    pub persuasion: (u8, Option<String>),
-    pub streetwise: (u8, Option<String>),
-    pub subterfuge: (u8, Option<String>),
-    pub academics: (u8, Option<String>),
-    pub awareness: (u8, Option<String>),
-    pub finance: (u8, Option<String>),
-    pub investigation: (u8, Option<String>),
-    pub medicine: (u8, Option<String>),
-    pub occult: (u8, Option<String>),
-    pub politics: (u8, Option<String>),
-    pub science: (u8, Option<String>),
-    pub technology: (u8, Option<String>),
+    pub athletics: SkillRating,
+    pub brawl: SkillRating,
+    pub craft: SkillRating,
+    pub drive: SkillRating,
+    pub firearms: SkillRating,
+    pub larceny: SkillRating,
+    pub melee: SkillRating,
+    pub stealth: SkillRating,
+    pub survival: SkillRating,
+    pub animal_ken: SkillRating,
+    pub etiquette: SkillRating,
+    pub insight: SkillRating,
+    pub intimidation: SkillRating,
+    pub leadership: SkillRating,
+    pub performance: SkillRating,
+    pub persuasion: SkillRating,
+    pub streetwise: SkillRating,
+    pub subterfuge: SkillRating,
+    pub academics: SkillRating,
+    pub awareness: SkillRating,
+    pub finance: SkillRating,
+    pub investigation: SkillRating,
+    pub medicine: SkillRating,
+    pub occult: SkillRating,
+    pub politics: SkillRating,
+    pub science: SkillRating,
+    pub technology: SkillRating,
+}
+
+impl Skills {
+    /// Set skills during character creation. `tiers` holds the skills picked for every
+    /// tier of the distribution, from the highest number of dots to the lowest; all
+    /// other skills stay at 0.
+    pub fn set_skills_during_creation(
+        &mut self,
+        distribution: SkillDistribution,
+        tiers: Vec<Vec<Skill>>,
+    ) -> Result<(), SkillDistributionError> {
+        distribution.check(&tiers)?;
+        *self = Self::default();
+        for ((dots, _), skills) in distribution.tiers().iter().zip(tiers) {
+            for skill in skills {
+                self[skill].dots = (*dots).into();
+            }
+        }
+        Ok(())
+    }
+
+    /// Skills with dots that need a specialty on a new character, see Core Rulebook, p. 159.
+    pub fn required_specialties(&self) -> Vec<Skill> {
+        Skill::NEED_SPECIALTY
+            .into_iter()
+            .filter(|&skill| self[skill].dots.value() > 0)
+            .collect()
+    }
+
+    /// Add a specialty to a skill, which needs at least one dot for it and can't have the
+    /// same specialty twice.
+    pub fn add_specialty(
+        &mut self,
+        skill: Skill,
+        specialty: impl Into<String>,
+    ) -> Result<(), SpecialtyError> {
+        let specialty = specialty.into();
+        if self[skill].dots.value() == 0 {
+            return Err(SpecialtyError::NoDots(skill));
+        }
+        let specialty = specialty.trim();
+        if specialty.is_empty() {
+            return Err(SpecialtyError::Empty);
+        }
+        if self[skill].has_specialty(specialty) {
+            return Err(SpecialtyError::Duplicate(skill, specialty.to_owned()));
+        }
+        self[skill].add_specialty(specialty);
+        Ok(())
+    }
+}
+
+/// The dots in a skill and the specialties taken in it.
+///
+/// Older sheets store a skill as `[dots, "specialty"]` or `[dots, null]`;
+/// those are still read transparently.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(try_from = "SkillRatingRepr")]
+pub struct SkillRating {
+    pub dots: Dots,
+    pub specialties: Vec<String>,
+}
+
+impl SkillRating {
+    pub fn new(dots: u8) -> Self {
+        Self {
+            dots: dots.into(),
+            specialties: Vec::new(),
+        }
+    }
+
+    /// Add a specialty, e.g. `SkillRating::new(3).with_specialty("Art History")`.
+    pub fn with_specialty(mut self, specialty: impl Into<String>) -> Self {
+        self.add_specialty(specialty);
+        self
+    }
+
+    /// Add a specialty, unless the skill already has it.
+    pub fn add_specialty(&mut self, specialty: impl Into<String>) {
+        let specialty = specialty.into();
+        if !self.has_specialty(&specialty) {
+            self.specialties.push(specialty);
+        }
+    }
+
+    /// Specialties are compared case-insensitively.
+    pub fn has_specialty(&self, specialty: &str) -> bool {
+        self.specialties
+            .iter()
+            .any(|s| s.eq_ignore_ascii_case(specialty))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SkillRatingRepr {
+    Legacy(u8, Option<String>),
+    Full {
+        dots: u8,
+        #[serde(default)]
+        specialties: Vec<String>,
+    },
+}
+
+impl TryFrom<SkillRatingRepr> for SkillRating {
+    type Error = RatingError;
+
+    fn try_from(repr: SkillRatingRepr) -> Result<Self, Self::Error> {
+        let (dots, specialties) = match repr {
+            SkillRatingRepr::Legacy(dots, specialty) => (dots, specialty.into_iter().collect()),
+            SkillRatingRepr::Full { dots, specialties } => (dots, specialties),
+        };
+        Ok(Self {
+            dots: Dots::checked(dots)?,
+            specialties,
+        })
+    }
+}
+
+/// Skills are read one by one, so that an out-of-range value can be reported
+/// with the skill it belongs to.
+impl<'de> Deserialize<'de> for Skills {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SkillsVisitor;
+
+        impl<'de> Visitor<'de> for SkillsVisitor {
+            type Value = Skills;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                "a map of skills to their ratings".fmt(f)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut skills = Skills::default();
+                let mut seen = Vec::new();
+                while let Some(skill) = map.next_key::<Skill>()? {
+                    skills[skill] =
+                        next_value_at(&mut map, format_args!("skills.{}", skill.key()))?;
+                    seen.push(skill);
+                }
+                match Skill::ALL.into_iter().find(|s| !seen.contains(s)) {
+                    Some(missing) => Err(A::Error::missing_field(missing.key())),
+                    None => Ok(skills),
+                }
+            }
+        }
+
+        deserializer.deserialize_map(SkillsVisitor)
+    }
 }
 
 /// We can use this to do something like
 /// let skill: Skill = Skill::Brawl/* say you get this from user input ... */;
 /// character.skills\[brawl] += 1;
 impl Index<Skill> for Skills {
-    type Output = (u8, Option<String>);
+    type Output = SkillRating;
 
     fn index(&self, skill: Skill) -> &Self::Output {
         match skill {
@@ -74,7 +225,42 @@ impl Index<Skill> for Skills {
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl IndexMut<Skill> for Skills {
+    fn index_mut(&mut self, skill: Skill) -> &mut Self::Output {
+        match skill {
+            Skill::Athletics => &mut self.athletics,
+            Skill::Brawl => &mut self.brawl,
+            Skill::Craft => &mut self.craft,
+            Skill::Drive => &mut self.drive,
+            Skill::Firearms => &mut self.firearms,
+            Skill::Larceny => &mut self.larceny,
+            Skill::Melee => &mut self.melee,
+            Skill::Stealth => &mut self.stealth,
+            Skill::Survival => &mut self.survival,
+            Skill::AnimalKen => &mut self.animal_ken,
+            Skill::Etiquette => &mut self.etiquette,
+            Skill::Insight => &mut self.insight,
+            Skill::Intimidation => &mut self.intimidation,
+            Skill::Leadership => &mut self.leadership,
+            Skill::Performance => &mut self.performance,
+            Skill::Persuasion => &mut self.persuasion,
+            Skill::Streetwise => &mut self.streetwise,
+            Skill::Subterfuge => &mut self.subterfuge,
+            Skill::Academics => &mut self.academics,
+            Skill::Awareness => &mut self.awareness,
+            Skill::Finance => &mut self.finance,
+            Skill::Investigation => &mut self.investigation,
+            Skill::Medicine => &mut self.medicine,
+            Skill::Occult => &mut self.occult,
+            Skill::Politics => &mut self.politics,
+            Skill::Science => &mut self.science,
+            Skill::Technology => &mut self.technology,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Skill {
     Athletics,
     Brawl,
@@ -105,6 +291,79 @@ pub enum Skill {
     Technology,
 }
 
+impl Skill {
+    pub const ALL: [Skill; 27] = [
+        Skill::Athletics,
+        Skill::Brawl,
+        Skill::Craft,
+        Skill::Drive,
+        Skill::Firearms,
+        Skill::Larceny,
+        Skill::Melee,
+        Skill::Stealth,
+        Skill::Survival,
+        Skill::AnimalKen,
+        Skill::Etiquette,
+        Skill::Insight,
+        Skill::Intimidation,
+        Skill::Leadership,
+        Skill::Performance,
+        Skill::Persuasion,
+        Skill::Streetwise,
+        Skill::Subterfuge,
+        Skill::Academics,
+        Skill::Awareness,
+        Skill::Finance,
+        Skill::Investigation,
+        Skill::Medicine,
+        Skill::Occult,
+        Skill::Politics,
+        Skill::Science,
+        Skill::Technology,
+    ];
+
+    /// Skills too broad to be used without a specialty.
+    pub const NEED_SPECIALTY: [Skill; 4] = [
+        Skill::Academics,
+        Skill::Craft,
+        Skill::Performance,
+        Skill::Science,
+    ];
+
+    /// The name of the skill as used on the character sheet.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Skill::Athletics => "athletics",
+            Skill::Brawl => "brawl",
+            Skill::Craft => "craft",
+            Skill::Drive => "drive",
+            Skill::Firearms => "firearms",
+            Skill::Larceny => "larceny",
+            Skill::Melee => "melee",
+            Skill::Stealth => "stealth",
+            Skill::Survival => "survival",
+            Skill::AnimalKen => "animal_ken",
+            Skill::Etiquette => "etiquette",
+            Skill::Insight => "insight",
+            Skill::Intimidation => "intimidation",
+            Skill::Leadership => "leadership",
+            Skill::Performance => "performance",
+            Skill::Persuasion => "persuasion",
+            Skill::Streetwise => "streetwise",
+            Skill::Subterfuge => "subterfuge",
+            Skill::Academics => "academics",
+            Skill::Awareness => "awareness",
+            Skill::Finance => "finance",
+            Skill::Investigation => "investigation",
+            Skill::Medicine => "medicine",
+            Skill::Occult => "occult",
+            Skill::Politics => "politics",
+            Skill::Science => "science",
+            Skill::Technology => "technology",
+        }
+    }
+}
+
 impl FromStr for Skill {
     type Err = ParseSkillError;
 
@@ -156,6 +415,160 @@ impl Display for ParseSkillError {
 
 impl std::error::Error for ParseSkillError {}
 
+/// The ways to spread skill dots for a new character, see Core Rulebook, p. 157.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkillDistribution {
+    JackOfAllTrades,
+    Balanced,
+    Specialist,
+}
+
+impl SkillDistribution {
+    pub const ALL: [SkillDistribution; 3] = [
+        SkillDistribution::JackOfAllTrades,
+        SkillDistribution::Balanced,
+        SkillDistribution::Specialist,
+    ];
+
+    /// How many skills get how many dots, as `(dots, number of skills)`.
+    pub fn tiers(&self) -> &'static [(u8, usize)] {
+        match self {
+            SkillDistribution::JackOfAllTrades => &[(3, 1), (2, 8), (1, 10)],
+            SkillDistribution::Balanced => &[(3, 3), (2, 5), (1, 7)],
+            SkillDistribution::Specialist => &[(4, 1), (3, 3), (2, 3), (1, 3)],
+        }
+    }
+
+    /// Check that the picked skills fill every tier, and that no skill is picked twice.
+    pub fn check(&self, tiers: &[Vec<Skill>]) -> Result<(), SkillDistributionError> {
+        if tiers.len() != self.tiers().len() {
+            return Err(SkillDistributionError::WrongTierCount {
+                expected: self.tiers().len(),
+                got: tiers.len(),
+            });
+        }
+        let mut seen = Vec::new();
+        for (&(dots, expected), skills) in self.tiers().iter().zip(tiers) {
+            if skills.len() != expected {
+                return Err(SkillDistributionError::WrongSkillCount {
+                    dots,
+                    expected,
+                    got: skills.len(),
+                });
+            }
+            for skill in skills {
+                if seen.contains(skill) {
+                    return Err(SkillDistributionError::Duplicate(*skill));
+                }
+                seen.push(*skill);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for SkillDistribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SkillDistribution::JackOfAllTrades => "Jack of All Trades",
+            SkillDistribution::Balanced => "Balanced",
+            SkillDistribution::Specialist => "Specialist",
+        };
+        let tiers: Vec<String> = self
+            .tiers()
+            .iter()
+            .map(|(dots, count)| format!("{count} at {dots}"))
+            .collect();
+        write!(f, "{name} ({})", tiers.join(", "))
+    }
+}
+
+impl FromStr for SkillDistribution {
+    type Err = ParseSkillDistributionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "jack of all trades" | "jack" => SkillDistribution::JackOfAllTrades,
+            "balanced" => SkillDistribution::Balanced,
+            "specialist" => SkillDistribution::Specialist,
+            _ => return Err(ParseSkillDistributionError),
+        })
+    }
+}
+
+/// An error that occurs when a `SkillDistribution` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `SkillDistribution`.
+#[derive(Debug)]
+pub struct ParseSkillDistributionError;
+
+impl Display for ParseSkillDistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match jack of all trades, balanced or specialist".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseSkillDistributionError {}
+
+/// An error that occurs when the picked skills don't match a [SkillDistribution].
+#[derive(Debug, PartialEq)]
+pub enum SkillDistributionError {
+    WrongTierCount {
+        expected: usize,
+        got: usize,
+    },
+    WrongSkillCount {
+        dots: u8,
+        expected: usize,
+        got: usize,
+    },
+    Duplicate(Skill),
+}
+
+impl Display for SkillDistributionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SkillDistributionError::WrongTierCount { expected, got } => {
+                write!(f, "expected {expected} tiers of skills, got {got}")
+            }
+            SkillDistributionError::WrongSkillCount {
+                dots,
+                expected,
+                got,
+            } => write!(f, "expected {expected} skills with {dots} dots, got {got}"),
+            SkillDistributionError::Duplicate(skill) => {
+                write!(f, "{skill:?} was picked more than once")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SkillDistributionError {}
+
+/// An error that occurs when a specialty can't be added to a skill.
+#[derive(Debug, PartialEq)]
+pub enum SpecialtyError {
+    NoDots(Skill),
+    Empty,
+    Duplicate(Skill, String),
+}
+
+impl Display for SpecialtyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpecialtyError::NoDots(skill) => {
+                write!(f, "{skill:?} has no dots, so it can't have a specialty")
+            }
+            SpecialtyError::Empty => write!(f, "a specialty needs a name"),
+            SpecialtyError::Duplicate(skill, specialty) => {
+                write!(f, "{skill:?} already has the specialty {specialty}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecialtyError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,6 +580,120 @@ mod tests {
         assert_eq!(skill, expected)
     }
 
+    #[test]
+    fn legacy_skill_format_is_read() {
+        let rating: SkillRating = serde_json::from_str(r#"[3, "plastic arts"]"#).unwrap();
+        assert_eq!(rating, SkillRating::new(3).with_specialty("plastic arts"));
+
+        let rating: SkillRating = serde_json::from_str("[2, null]").unwrap();
+        assert_eq!(rating, SkillRating::new(2));
+    }
+
+    #[test]
+    fn skill_out_of_range_names_the_field() {
+        let mut json = serde_json::to_value(Skills::default()).unwrap();
+        json["brawl"] = serde_json::json!([6, null]);
+        let error = serde_json::from_value::<Skills>(json).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "skills.brawl: 6 is out of range, must be between 0 and 5"
+        );
+    }
+
+    #[test]
+    fn multiple_specialties_round_trip() {
+        let rating = SkillRating::new(3)
+            .with_specialty("Art History")
+            .with_specialty("Occult Lore");
+
+        let json = serde_json::to_string(&rating).unwrap();
+        assert_eq!(serde_json::from_str::<SkillRating>(&json).unwrap(), rating);
+    }
+
+    #[test]
+    fn specialist_distribution() {
+        let mut skills = Skills::default();
+        let tiers = vec![
+            vec![Skill::Occult],
+            vec![Skill::Academics, Skill::Investigation, Skill::Awareness],
+            vec![Skill::Insight, Skill::Stealth, Skill::Science],
+            vec![Skill::Brawl, Skill::Etiquette, Skill::Drive],
+        ];
+
+        skills
+            .set_skills_during_creation(SkillDistribution::Specialist, tiers)
+            .unwrap();
+
+        assert_eq!(skills.occult, SkillRating::new(4));
+        assert_eq!(skills.awareness, SkillRating::new(3));
+        assert_eq!(skills.drive, SkillRating::new(1));
+        assert_eq!(skills.firearms, SkillRating::new(0));
+    }
+
+    #[test]
+    fn distribution_must_be_satisfied() {
+        let distribution = SkillDistribution::Specialist;
+
+        assert_eq!(
+            distribution.check(&[vec![Skill::Occult]]),
+            Err(SkillDistributionError::WrongTierCount {
+                expected: 4,
+                got: 1
+            })
+        );
+        assert_eq!(
+            distribution.check(&[
+                vec![Skill::Occult],
+                vec![Skill::Academics, Skill::Investigation],
+                vec![],
+                vec![],
+            ]),
+            Err(SkillDistributionError::WrongSkillCount {
+                dots: 3,
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(
+            distribution.check(&[
+                vec![Skill::Occult],
+                vec![Skill::Academics, Skill::Investigation, Skill::Occult],
+                vec![Skill::Insight, Skill::Stealth, Skill::Science],
+                vec![Skill::Brawl, Skill::Etiquette, Skill::Drive],
+            ]),
+            Err(SkillDistributionError::Duplicate(Skill::Occult))
+        );
+    }
+
+    #[test]
+    fn specialties_need_dots() {
+        let mut skills = Skills {
+            craft: SkillRating::new(2),
+            brawl: SkillRating::new(1),
+            ..Default::default()
+        };
+
+        assert_eq!(skills.required_specialties(), vec![Skill::Craft]);
+        assert_eq!(
+            skills.add_specialty(Skill::Science, "Chemistry"),
+            Err(SpecialtyError::NoDots(Skill::Science))
+        );
+
+        skills.add_specialty(Skill::Craft, "Sculpting").unwrap();
+        skills.add_specialty(Skill::Brawl, "Grappling").unwrap();
+        assert!(skills.craft.has_specialty("sculpting"));
+        assert!(skills.brawl.has_specialty("Grappling"));
+        assert_eq!(
+            skills.add_specialty(Skill::Brawl, " grappling "),
+            Err(SpecialtyError::Duplicate(
+                Skill::Brawl,
+                String::from("grappling")
+            ))
+        );
+        assert_eq!(skills.brawl.specialties.len(), 1);
+    }
+
     #[test]
     #[should_panic]
     fn non_existing_skill_from_string() {
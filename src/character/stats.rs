@@ -1,5 +1,12 @@
+use crate::character::blood::RouseCheck;
+use crate::character::rating::{Rating, RatingError};
 use crate::character::{Attribute, Character};
+use crate::dice::Roll;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
 
 #[derive(Default, Debug, PartialEq)]
 pub struct Health {
@@ -30,21 +37,139 @@ impl Health {
         };
 
         Self {
-            value: &character.attributes[Attribute::Stamina] + 3,
+            value: character.attributes[Attribute::Stamina].value() + 3,
             damage,
         }
     }
+
+    /// A character whose Health track is full is impaired, see Core Rulebook, p. 126.
+    pub fn is_impaired(&self) -> bool {
+        self.damage.fills(self.value)
+    }
+
+    pub fn state(&self) -> TrackState {
+        self.damage.state(self.value)
+    }
 }
 
 /// Rather `Damage` than health since we will always be able to calculate max
 /// health at runtime. Superficial and aggravated damage, however, must be
 /// tracked on the sheet.
-#[derive(Serialize, Deserialize, Default, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Default, PartialEq, Debug, Clone, Copy)]
 pub struct Damage {
     pub superficial: u8,
     pub aggravated: u8,
 }
 
+impl Damage {
+    /// Whether the damage fills every box of a track with `track` boxes.
+    pub fn fills(&self, track: u8) -> bool {
+        u16::from(self.superficial) + u16::from(self.aggravated) >= u16::from(track)
+    }
+
+    /// Mark damage on a track with `track` boxes, see Core Rulebook, p. 126.
+    ///
+    /// Damage goes into empty boxes first. Once the track is full, every further point
+    /// turns a superficial box into an aggravated one. Damage beyond a track that is all
+    /// aggravated is still counted, so that the sheet records the Final Death.
+    pub fn take(&mut self, kind: DamageKind, amount: u8, track: u8) {
+        for _ in 0..amount {
+            if !self.fills(track) {
+                match kind {
+                    DamageKind::Superficial => self.superficial += 1,
+                    DamageKind::Aggravated => self.aggravated += 1,
+                }
+            } else if self.superficial > 0 {
+                self.superficial -= 1;
+                self.aggravated = self.aggravated.saturating_add(1);
+            } else {
+                self.aggravated = self.aggravated.saturating_add(1);
+            }
+        }
+    }
+
+    /// Vampires halve superficial physical damage, rounding up.
+    pub fn halved(amount: u8) -> u8 {
+        amount.div_ceil(2)
+    }
+
+    pub fn state(&self, track: u8) -> TrackState {
+        if self.aggravated > track {
+            TrackState::FinalDeath
+        } else if self.aggravated >= track {
+            TrackState::Torpor
+        } else if self.fills(track) {
+            TrackState::Impaired
+        } else {
+            TrackState::Healthy
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageKind {
+    Superficial,
+    Aggravated,
+}
+
+impl FromStr for DamageKind {
+    type Err = ParseDamageKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "s" | "superficial" => DamageKind::Superficial,
+            "a" | "aggravated" => DamageKind::Aggravated,
+            _ => return Err(ParseDamageKindError),
+        })
+    }
+}
+
+/// An error that occurs when a `DamageKind` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `DamageKind`.
+#[derive(Debug)]
+pub struct ParseDamageKindError;
+
+impl Display for ParseDamageKindError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match superficial or aggravated".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseDamageKindError {}
+
+/// How badly a damage track is filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TrackState {
+    Healthy,
+    /// The track is full: -2 dice to the affected pools.
+    Impaired,
+    /// The Health track is full of aggravated damage.
+    Torpor,
+    /// Aggravated damage beyond a Health track in torpor.
+    FinalDeath,
+}
+
+impl Display for TrackState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrackState::Healthy => "healthy",
+            TrackState::Impaired => "impaired",
+            TrackState::Torpor => "in torpor",
+            TrackState::FinalDeath => "met the Final Death",
+        }
+        .fmt(f)
+    }
+}
+
+/// Health healed through Rouse Checks, see Core Rulebook, p. 128.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Healing {
+    pub rouse_checks: Vec<RouseCheck>,
+    pub healed: u8,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Willpower {
     pub value: u8,
@@ -55,19 +180,34 @@ impl Willpower {
     /// Willpower is calculated from a character's [Attribute::Composure] + [Attribute::Resolve]] values
     pub fn from_character(character: &Character) -> Self {
         Self {
-            value: character.attributes[Attribute::Composure]
-                + character.attributes[Attribute::Resolve],
-            damage: Damage {
-                superficial: character.willpower_damage.superficial,
-                aggravated: character.willpower_damage.aggravated,
-            },
+            value: character.attributes[Attribute::Composure].value()
+                + character.attributes[Attribute::Resolve].value(),
+            damage: character.willpower_damage,
         }
     }
+
+    /// A character whose Willpower track is full is impaired for social and mental rolls.
+    pub fn is_impaired(&self) -> bool {
+        self.damage.fills(self.value)
+    }
+
+    /// Willpower left to spend: the boxes not marked with damage.
+    pub fn current(&self) -> u8 {
+        self.value
+            .saturating_sub(self.damage.superficial)
+            .saturating_sub(self.damage.aggravated)
+    }
+
+    /// Willpower damage can impair a character, but never send them into torpor.
+    pub fn state(&self) -> TrackState {
+        self.damage.state(self.value).min(TrackState::Impaired)
+    }
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawHumanity")]
 pub struct Humanity {
-    pub value: u8,
+    pub value: Rating<10>,
     pub stains: u8,
 }
 
@@ -75,12 +215,42 @@ impl Default for Humanity {
     /// We don't want humanity to default to 0, since that would mean our vampire has fallen to the Beast.
     fn default() -> Self {
         Self {
-            value: 7,
+            value: 7.into(),
             stains: 0,
         }
     }
 }
 
+/// Humanity as it is written on the sheet, before checking its range.
+#[derive(Deserialize)]
+struct RawHumanity {
+    value: u8,
+    stains: u8,
+}
+
+impl TryFrom<RawHumanity> for Humanity {
+    type Error = HumanityError;
+
+    fn try_from(raw: RawHumanity) -> Result<Self, Self::Error> {
+        Ok(Self {
+            value: Rating::checked(raw.value).map_err(HumanityError)?,
+            stains: raw.stains,
+        })
+    }
+}
+
+/// An error that occurs when a sheet has a Humanity value out of range.
+#[derive(Debug, PartialEq)]
+pub struct HumanityError(RatingError);
+
+impl Display for HumanityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "humanity.value: {}", self.0)
+    }
+}
+
+impl std::error::Error for HumanityError {}
+
 impl Humanity {
     /// The default is to start with humanity 7, but exceptions apply:
     ///
@@ -93,7 +263,7 @@ impl Humanity {
     /// Ancillae start with their humanity reduced by one.
     pub fn new_for_ancilla(&self) -> Self {
         Self {
-            value: 6,
+            value: 6.into(),
             stains: 0,
         }
     }
@@ -104,6 +274,122 @@ impl Humanity {
             stains: character.humanity.stains,
         }
     }
+
+    /// Boxes of the Humanity track that are neither filled nor stained.
+    pub fn unmarked_boxes(&self) -> u8 {
+        (10 - self.value.value()).saturating_sub(self.stains)
+    }
+
+    /// Add stains and return how many of them didn't fit into the empty boxes. Each of
+    /// those deals one aggravated Willpower damage, see Core Rulebook, p. 239.
+    pub fn add_stains(&mut self, stains: u8) -> u8 {
+        let overflow = stains.saturating_sub(self.unmarked_boxes());
+        self.stains = self.stains.saturating_add(stains);
+        overflow
+    }
+
+    /// A character with more stains than empty boxes is impaired until the next Remorse roll.
+    pub fn is_impaired(&self) -> bool {
+        self.stains > 10 - self.value.value()
+    }
+
+    /// Make the Remorse roll at the end of the session if there are any stains; Core
+    /// Rulebook, p. 239. The pool is one die per unmarked box, but at least one. On a
+    /// failure, Humanity drops by one. The stains are removed either way, as the rules
+    /// say, not only on a success.
+    pub fn remorse(&mut self, rng: &mut impl Rng) -> Option<Remorse> {
+        if self.stains == 0 {
+            return None;
+        }
+        let roll = Roll::new(self.unmarked_boxes().max(1), 0, rng);
+        let lost_humanity = !roll.outcome(1).is_win();
+        if lost_humanity {
+            self.value = self.value.value().saturating_sub(1).into();
+        }
+        self.stains = 0;
+        Some(Remorse {
+            roll,
+            lost_humanity,
+        })
+    }
+
+    /// What the current Humanity means in play.
+    pub fn effects(&self) -> HumanityEffects {
+        HumanityEffects::for_value(self.value.value())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Remorse {
+    pub roll: Roll,
+    pub lost_humanity: bool,
+}
+
+/// The effects of a Humanity rating according to the Core Rulebook, p. 236ff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HumanityEffects {
+    pub blush_of_life: BlushOfLife,
+    pub food: Food,
+    /// Dice pools while awake during the day can't exceed Humanity.
+    pub daytime_pool_cap: u8,
+    /// How long the vampire stays in torpor.
+    pub torpor_length: &'static str,
+}
+
+/// What the Blush of Life does for a vampire's appearance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlushOfLife {
+    /// The vampire passes for a pale mortal without it.
+    NotNeeded,
+    /// A Rouse Check makes the vampire pass for mortal.
+    Convincing,
+    /// Even with a Rouse Check the vampire only looks sickly.
+    Sickly,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Food {
+    /// The vampire can eat and drink like a mortal.
+    Digests,
+    /// Food and drink stay down until the vampire has to vomit them up later that night.
+    KeepsDown,
+    /// Food and drink come right back up.
+    Vomits,
+}
+
+impl HumanityEffects {
+    pub fn for_value(humanity: u8) -> Self {
+        let blush_of_life = match humanity {
+            9.. => BlushOfLife::NotNeeded,
+            6..=8 => BlushOfLife::Convincing,
+            _ => BlushOfLife::Sickly,
+        };
+        let food = match humanity {
+            10.. => Food::Digests,
+            8..=9 => Food::KeepsDown,
+            _ => Food::Vomits,
+        };
+        let torpor_length = match humanity {
+            10.. => "one day",
+            9 => "three days",
+            8 => "one week",
+            7 => "two weeks",
+            6 => "one month",
+            5 => "one year",
+            4 => "one decade",
+            3 => "five decades",
+            2 => "one century",
+            1 => "five centuries",
+            0 => "a millennium or more",
+        };
+
+        Self {
+            blush_of_life,
+            food,
+            daytime_pool_cap: humanity,
+            torpor_length,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -126,7 +412,7 @@ mod tests {
     fn health_calculated_correctly() {
         use crate::character::attributes::Attributes;
         let mut attributes = Attributes::default();
-        attributes[Attribute::Stamina] = 3;
+        attributes[Attribute::Stamina] = 3.into();
 
         let char = Character::builder()
             .player_name(String::from("Juke"))
@@ -157,6 +443,77 @@ mod tests {
         assert_eq!(Willpower::from_character(&char), expected_wp);
     }
 
+    #[test]
+    fn full_track_converts_superficial_to_aggravated() {
+        let mut damage = Damage::default();
+        damage.take(DamageKind::Superficial, 4, 5);
+        assert_eq!(damage.state(5), TrackState::Healthy);
+
+        damage.take(DamageKind::Superficial, 2, 5);
+        assert_eq!(
+            damage,
+            Damage {
+                superficial: 4,
+                aggravated: 1
+            }
+        );
+        assert_eq!(damage.state(5), TrackState::Impaired);
+
+        damage.take(DamageKind::Aggravated, 1, 5);
+        assert_eq!(
+            damage,
+            Damage {
+                superficial: 3,
+                aggravated: 2
+            }
+        );
+    }
+
+    #[test]
+    fn aggravated_damage_leads_to_torpor_and_final_death() {
+        let mut damage = Damage {
+            superficial: 2,
+            aggravated: 2,
+        };
+        damage.take(DamageKind::Aggravated, 2, 4);
+        assert_eq!(damage.state(4), TrackState::Torpor);
+
+        damage.take(DamageKind::Superficial, 1, 4);
+        assert_eq!(damage.state(4), TrackState::FinalDeath);
+    }
+
+    #[test]
+    fn damage_beyond_u8_does_not_overflow() {
+        let mut damage = Damage {
+            superficial: 250,
+            aggravated: 6,
+        };
+        assert!(damage.fills(7));
+        assert_eq!(damage.state(7), TrackState::Impaired);
+
+        damage.aggravated = u8::MAX;
+        damage.take(DamageKind::Aggravated, 1, 7);
+        assert_eq!(damage.aggravated, u8::MAX);
+    }
+
+    #[test]
+    fn superficial_damage_is_halved_rounding_up() {
+        assert_eq!(Damage::halved(5), 3);
+        assert_eq!(Damage::halved(4), 2);
+    }
+
+    #[test]
+    fn willpower_never_goes_to_torpor() {
+        let willpower = Willpower {
+            value: 3,
+            damage: Damage {
+                superficial: 0,
+                aggravated: 4,
+            },
+        };
+        assert_eq!(willpower.state(), TrackState::Impaired);
+    }
+
     #[test]
     fn humanity_7_for_neonates() {
         let char = Character::from_file(std::path::PathBuf::from(
@@ -165,13 +522,72 @@ mod tests {
         .expect("sample_char.json should contain valid character json!");
 
         let expected_humanity = Humanity {
-            value: 7,
+            value: 7.into(),
             stains: 1,
         };
 
         assert_eq!(Humanity::from_character(&char), expected_humanity);
     }
 
+    #[test]
+    fn humanity_out_of_range_is_rejected_on_load() {
+        let error = serde_json::from_str::<Humanity>(r#"{"value": 11, "stains": 0}"#).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .starts_with("humanity.value: 11 is out of range"));
+    }
+
+    #[test]
+    fn stains_beyond_the_empty_boxes_overflow() {
+        let mut humanity = Humanity::default();
+        assert_eq!(humanity.add_stains(2), 0);
+        assert_eq!(humanity.unmarked_boxes(), 1);
+        assert!(!humanity.is_impaired());
+
+        assert_eq!(humanity.add_stains(2), 1);
+        assert_eq!(humanity.unmarked_boxes(), 0);
+        assert!(humanity.is_impaired());
+    }
+
+    #[test]
+    fn stains_saturate_instead_of_overflowing() {
+        let mut humanity = Humanity::default();
+        humanity.add_stains(200);
+        assert_eq!(humanity.add_stains(100), 100);
+        assert_eq!(humanity.stains, u8::MAX);
+        assert!(humanity.is_impaired());
+    }
+
+    #[test]
+    fn failed_remorse_costs_humanity_and_clears_stains() {
+        // always rolls a 1
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        let mut humanity = Humanity::default();
+
+        assert_eq!(humanity.remorse(&mut rng), None);
+
+        humanity.add_stains(4);
+        let remorse = humanity.remorse(&mut rng).unwrap();
+        assert_eq!(remorse.roll.dice.len(), 1);
+        assert!(remorse.lost_humanity);
+        assert_eq!(
+            humanity,
+            Humanity {
+                value: 6.into(),
+                stains: 0
+            }
+        );
+    }
+
+    #[test]
+    fn humanity_effects() {
+        let effects = Humanity::default().effects();
+        assert_eq!(effects.blush_of_life, BlushOfLife::Convincing);
+        assert_eq!(effects.food, Food::Vomits);
+        assert_eq!(effects.torpor_length, "two weeks");
+    }
+
     #[test]
     fn humanity_6_for_ancillae() {
         // TODO: write ancilla to file, then use from_file
@@ -179,21 +595,29 @@ mod tests {
             player_name: "".to_string(),
             character_name: "".to_string(),
             chronicle: "".to_string(),
+            clan: None,
+            predator_type: None,
             attributes: Default::default(),
             skills: Default::default(),
+            disciplines: Default::default(),
+            advantages: Default::default(),
+            experience: Default::default(),
+            blood_bonds: Default::default(),
             damage: Default::default(),
             willpower_damage: Default::default(),
             humanity: Humanity {
-                value: 6,
+                value: 6.into(),
                 stains: 0,
             },
             blood_potency: Default::default(),
             generation: 13.into(),
             hunger: Default::default(),
+            resonance: None,
+            compulsion_active: false,
         };
 
         let expected_humanity = Humanity {
-            value: 6,
+            value: 6.into(),
             stains: 0,
         };
 
@@ -0,0 +1,368 @@
+use crate::character::skills::Skill;
+use crate::character::stats::{Damage, DamageKind, TrackState};
+use crate::character::{Attribute, Character};
+use crate::dice::contest::{Contest, Opposition, Side};
+use crate::dice::pool::DicePool;
+use anyhow::Result;
+use rand::Rng;
+use std::fmt;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+/// Someone taking part in a conflict. Characters loaded from a sheet remember their
+/// file so that the results can be written back; NPCs made up on the spot don't.
+#[derive(Debug, PartialEq)]
+pub struct Participant {
+    pub character: Character,
+    pub path: Option<PathBuf>,
+}
+
+impl Participant {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self {
+            character: Character::from_file(&path)?,
+            path: Some(path.as_ref().to_path_buf()),
+        })
+    }
+
+    pub fn npc(character: Character) -> Self {
+        Self {
+            character,
+            path: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.character.character_name
+    }
+
+    pub fn state(&self) -> TrackState {
+        self.character.health().state()
+    }
+}
+
+/// An attack, e.g. Strength + Brawl for superficial damage, or Dexterity + Firearms
+/// with a weapon adding two damage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attack {
+    pub attribute: Attribute,
+    pub skill: Skill,
+    pub specialty: Option<String>,
+    pub kind: DamageKind,
+    /// Damage the weapon adds to a hit.
+    pub weapon: u8,
+}
+
+/// How the defender avoids the attack, e.g. Dexterity + Athletics to dodge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Defence {
+    pub attribute: Attribute,
+    pub skill: Skill,
+}
+
+/// The result of one attack against one defender.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exchange {
+    pub attacker: String,
+    pub defender: String,
+    /// The attack rolled against the defence.
+    pub contest: Contest,
+    /// Damage marked on the defender's Health, after halving superficial damage.
+    pub damage: u8,
+    pub defender_state: TrackState,
+}
+
+impl Display for Exchange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} attacks {}: {}",
+            self.attacker, self.defender, self.contest
+        )?;
+        match self.damage {
+            0 => write!(f, ", no damage"),
+            damage => write!(
+                f,
+                ", {damage} damage ({}: {})",
+                self.defender, self.defender_state
+            ),
+        }
+    }
+}
+
+/// A combat scene, run as rounds of attacks and defences; see Core Rulebook, p. 123ff.
+#[derive(Debug, PartialEq)]
+pub struct Conflict {
+    pub participants: Vec<Participant>,
+    pub round: u32,
+}
+
+impl Conflict {
+    pub fn new(participants: Vec<Participant>) -> Self {
+        Self {
+            participants,
+            round: 1,
+        }
+    }
+
+    /// Participants who can still act, i.e. neither in torpor nor dead.
+    pub fn active(&self) -> impl Iterator<Item = (usize, &Participant)> {
+        self.participants
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.state() < TrackState::Torpor)
+    }
+
+    /// Roll an attack against a defence. If the attacker gets more successes, the
+    /// margin plus the weapon's damage goes onto the defender's Health track. A messy
+    /// critical or bestial failure on the attack triggers the attacker's compulsion.
+    ///
+    /// Nobody can attack themselves, and both sides have to be neither in torpor nor dead.
+    pub fn exchange(
+        &mut self,
+        attacker: usize,
+        attack: &Attack,
+        defender: usize,
+        defence: &Defence,
+        rng: &mut impl Rng,
+    ) -> Result<Exchange, ConflictError> {
+        for index in [attacker, defender] {
+            if index >= self.participants.len() {
+                return Err(ConflictError::NoSuchParticipant(index));
+            }
+        }
+        if attacker == defender {
+            return Err(ConflictError::SelfAttack(
+                self.participants[attacker].name().to_owned(),
+            ));
+        }
+        let state = self.participants[attacker].state();
+        if state >= TrackState::Torpor {
+            return Err(ConflictError::CannotAct {
+                name: self.participants[attacker].name().to_owned(),
+                state,
+            });
+        }
+        let state = self.participants[defender].state();
+        if state >= TrackState::Torpor {
+            return Err(ConflictError::AlreadyDown {
+                name: self.participants[defender].name().to_owned(),
+                state,
+            });
+        }
+
+        let attack_pool = DicePool::from_character(
+            &self.participants[attacker].character,
+            attack.attribute,
+            attack.skill,
+            attack.specialty.as_deref(),
+        );
+        let defence_pool = DicePool::from_character(
+            &self.participants[defender].character,
+            defence.attribute,
+            defence.skill,
+            None,
+        );
+        let contest = Contest::resolve(&attack_pool, &Opposition::Pool(defence_pool), rng);
+        self.participants[attacker]
+            .character
+            .trigger_compulsion(contest.outcome);
+
+        let target = &mut self.participants[defender].character;
+        let (damage, defender_state) = match contest.winner {
+            Some(Side::Active) => {
+                let damage = u8::try_from(contest.margin)
+                    .unwrap_or(u8::MAX)
+                    .saturating_add(attack.weapon);
+                let state = target.take_damage(attack.kind, damage);
+                match attack.kind {
+                    DamageKind::Superficial => (Damage::halved(damage), state),
+                    DamageKind::Aggravated => (damage, state),
+                }
+            }
+            _ => (0, target.health().state()),
+        };
+
+        Ok(Exchange {
+            attacker: self.participants[attacker].name().to_owned(),
+            defender: self.participants[defender].name().to_owned(),
+            contest,
+            damage,
+            defender_state,
+        })
+    }
+
+    pub fn next_round(&mut self) {
+        self.round += 1;
+    }
+
+    /// Everyone's name and how they came out of the conflict.
+    pub fn summary(&self) -> Vec<(&str, TrackState)> {
+        self.participants
+            .iter()
+            .map(|p| (p.name(), p.state()))
+            .collect()
+    }
+
+    /// Write every participant that came from a sheet back to their file.
+    pub fn save(&self) -> Result<()> {
+        for participant in &self.participants {
+            if let Some(path) = &participant.path {
+                participant.character.to_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An error that occurs when an exchange can't take place.
+#[derive(Debug, PartialEq)]
+pub enum ConflictError {
+    NoSuchParticipant(usize),
+    SelfAttack(String),
+    CannotAct { name: String, state: TrackState },
+    AlreadyDown { name: String, state: TrackState },
+}
+
+impl Display for ConflictError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConflictError::NoSuchParticipant(index) => {
+                write!(f, "there is no participant number {}", index + 1)
+            }
+            ConflictError::SelfAttack(name) => write!(f, "{name} can't attack themselves"),
+            ConflictError::CannotAct { name, state } => {
+                write!(f, "{name} can't act ({state})")
+            }
+            ConflictError::AlreadyDown { name, state } => {
+                write!(f, "{name} can't be attacked ({state})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConflictError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::Outcome;
+    use rand::rngs::mock::StepRng;
+
+    fn fighter(name: &str, dexterity: u8) -> Participant {
+        let mut character = Character::builder()
+            .player_name(String::from("Storyteller"))
+            .character_name(String::from(name))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .build();
+        character.attributes.set_all_to_2();
+        character.attributes.dexterity = dexterity.into();
+        Participant::npc(character)
+    }
+
+    fn punch() -> Attack {
+        Attack {
+            attribute: Attribute::Dexterity,
+            skill: Skill::Brawl,
+            specialty: None,
+            kind: DamageKind::Superficial,
+            weapon: 0,
+        }
+    }
+
+    fn dodge() -> Defence {
+        Defence {
+            attribute: Attribute::Dexterity,
+            skill: Skill::Athletics,
+        }
+    }
+
+    #[test]
+    fn margin_becomes_damage() {
+        // every die shows a 10
+        let mut rng = StepRng::new(0xF000_0000, 0);
+        let mut conflict = Conflict::new(vec![fighter("Brawler", 5), fighter("Victim", 2)]);
+
+        let exchange = conflict
+            .exchange(0, &punch(), 1, &dodge(), &mut rng)
+            .unwrap();
+
+        // 5 tens are 9 successes, 2 tens are 4: a margin of 5, halved to 3
+        assert_eq!(exchange.damage, 3);
+        assert_eq!(conflict.participants[1].character.damage.superficial, 3);
+        assert_eq!(exchange.contest.outcome, Outcome::CriticalWin);
+    }
+
+    #[test]
+    fn huge_weapon_damage_does_not_overflow() {
+        // every die shows a 10
+        let mut rng = StepRng::new(0xF000_0000, 0);
+        let mut conflict = Conflict::new(vec![fighter("Brawler", 5), fighter("Victim", 2)]);
+        let attack = Attack {
+            kind: DamageKind::Aggravated,
+            weapon: u8::MAX,
+            ..punch()
+        };
+
+        let exchange = conflict
+            .exchange(0, &attack, 1, &dodge(), &mut rng)
+            .unwrap();
+        assert_eq!(exchange.damage, u8::MAX);
+        assert_eq!(exchange.defender_state, TrackState::FinalDeath);
+    }
+
+    #[test]
+    fn failed_attacks_do_no_damage() {
+        // every die shows a 1
+        let mut rng = StepRng::new(0, 0);
+        let mut conflict = Conflict::new(vec![fighter("Brawler", 5), fighter("Victim", 2)]);
+
+        let exchange = conflict
+            .exchange(0, &punch(), 1, &dodge(), &mut rng)
+            .unwrap();
+        assert_eq!(exchange.damage, 0);
+        assert_eq!(
+            conflict.summary(),
+            vec![
+                ("Brawler", TrackState::Healthy),
+                ("Victim", TrackState::Healthy)
+            ]
+        );
+    }
+
+    #[test]
+    fn torpid_participants_cannot_attack() {
+        let mut rng = StepRng::new(0, 0);
+        let mut conflict = Conflict::new(vec![fighter("Brawler", 5), fighter("Victim", 2)]);
+        conflict.participants[0].character.damage.aggravated = 5;
+
+        assert_eq!(
+            conflict.exchange(0, &punch(), 1, &dodge(), &mut rng),
+            Err(ConflictError::CannotAct {
+                name: String::from("Brawler"),
+                state: TrackState::Torpor
+            })
+        );
+        assert_eq!(conflict.active().count(), 1);
+    }
+
+    #[test]
+    fn only_others_still_standing_can_be_attacked() {
+        let mut rng = StepRng::new(0, 0);
+        let mut conflict = Conflict::new(vec![fighter("Brawler", 5), fighter("Victim", 2)]);
+
+        assert_eq!(
+            conflict.exchange(0, &punch(), 0, &dodge(), &mut rng),
+            Err(ConflictError::SelfAttack(String::from("Brawler")))
+        );
+
+        conflict.participants[1].character.damage.aggravated = 10;
+        assert_eq!(
+            conflict.exchange(0, &punch(), 1, &dodge(), &mut rng),
+            Err(ConflictError::AlreadyDown {
+                name: String::from("Victim"),
+                state: TrackState::FinalDeath
+            })
+        );
+    }
+}
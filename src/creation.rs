@@ -0,0 +1,506 @@
+pub mod generate;
+
+use crate::character::advantages::{Advantage, AdvantageKind};
+use crate::character::attributes::Attributes;
+use crate::character::clan::Clan;
+use crate::character::predator::{OfferedSpecialty, PredatorChoiceError, PredatorType};
+use crate::character::sea_of_time::{SeaOfTime, SeaOfTimeError};
+use crate::character::skills::{Skill, SkillDistribution, Skills};
+use crate::character::{Attribute, Character};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Every answer the interactive character creation asks for, so that characters can
+/// be created from a TOML or JSON file instead.
+///
+/// Answers are written the same way they would be typed into the prompts, e.g.
+/// `highest = "d"` or `clan = "Banu Haqim"`. Everything is optional while reading
+/// the file, so that [CreationAnswers::build] can report all missing answers at once.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CreationAnswers {
+    pub player_name: Option<String>,
+    pub character_name: Option<String>,
+    pub chronicle: Option<String>,
+    pub clan: Option<String>,
+    pub attributes: AttributeAnswers,
+    pub skills: SkillAnswers,
+    pub sea_of_time: Option<String>,
+    pub generation: Option<u8>,
+    pub predator: PredatorAnswers,
+    pub advantages: Vec<AdvantageAnswer>,
+    /// Where to write the character sheet.
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AttributeAnswers {
+    pub highest: Option<String>,
+    pub lowest: Option<String>,
+    pub threes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SkillAnswers {
+    pub distribution: Option<String>,
+    /// The skills of every tier of the distribution, from most to fewest dots.
+    pub tiers: Vec<Vec<String>>,
+    /// Specialties for Academics, Craft, Performance and Science, by skill.
+    pub specialties: BTreeMap<String, String>,
+    pub free_specialty: Option<FreeSpecialty>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FreeSpecialty {
+    pub skill: String,
+    pub specialty: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PredatorAnswers {
+    #[serde(rename = "type")]
+    pub predator_type: Option<String>,
+    /// One of the specialties the predator type offers, as the skill and the specialty
+    /// in parentheses, e.g. "Brawl (Grappling)". Where a kind of specialty is offered,
+    /// name the actual one, e.g. "Animal Ken (Cats)" for "Specific Animal".
+    pub specialty: Option<String>,
+    pub discipline: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AdvantageAnswer {
+    pub kind: String,
+    pub name: String,
+    pub dots: u8,
+    pub note: Option<String>,
+}
+
+impl CreationAnswers {
+    /// Read answers from a `.toml` file, or from JSON for any other file ending.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("could not read answers from {}", path.display()))?;
+        if path.extension().is_some_and(|e| e == "toml") {
+            Ok(toml::from_str(&content)?)
+        } else {
+            Ok(serde_json::from_str(&content)?)
+        }
+    }
+
+    /// Create the character, running the same checks as the interactive creation,
+    /// and return it together with the path to write it to.
+    pub fn build(&self) -> Result<(Character, PathBuf), AnswersError> {
+        let mut problems = Problems::default();
+
+        let player_name = problems.required("player_name", &self.player_name);
+        let character_name = problems.required("character_name", &self.character_name);
+        let chronicle = problems.required("chronicle", &self.chronicle);
+        let clan: Option<Clan> = problems.parse("clan", &self.clan);
+        let attributes = self.attributes.build(&mut problems);
+        let skills = self.skills.build(&mut problems);
+        let sea_of_time: Option<SeaOfTime> = problems.parse("sea_of_time", &self.sea_of_time);
+        let generation = problems.required("generation", &self.generation);
+        let output = problems.required("output", &self.output);
+
+        let mut character = Character::builder()
+            .player_name(player_name.unwrap_or_default())
+            .character_name(character_name.unwrap_or_default())
+            .chronicle(chronicle.unwrap_or_default())
+            .maybe_clan(clan)
+            .attributes(attributes.unwrap_or_default())
+            .skills(skills.unwrap_or_default())
+            // new characters start with Hunger 1 by default
+            .hunger(1.into())
+            .build();
+        if let (Some(_), Some(sea_of_time), Some(generation)) = (clan, sea_of_time, generation) {
+            match sea_of_time.apply(&mut character, generation) {
+                Ok(()) => {}
+                Err(e @ SeaOfTimeError::Generation { .. }) => problems.push("generation", e),
+                Err(e @ SeaOfTimeError::ThinBlood(_)) => problems.push("sea_of_time", e),
+            }
+        }
+        self.predator.apply(&mut character, &mut problems);
+
+        for (i, answer) in self.advantages.iter().enumerate() {
+            let kind: Option<AdvantageKind> =
+                problems.parse_str(format!("advantages[{i}].kind"), &answer.kind);
+            let advantage = Advantage::new(&answer.name, answer.dots, answer.note.clone());
+            let added = match kind {
+                Some(kind) => character.advantages.add(kind, advantage),
+                None => advantage.check_dots(),
+            };
+            if let Err(e) = added {
+                problems.push(format!("advantages[{i}].dots"), e);
+            }
+        }
+        let budget = sea_of_time.unwrap_or(SeaOfTime::Neonate).advantage_budget();
+        if let Err(e) = character.advantages.check_creation(&budget) {
+            problems.push("advantages", e);
+        }
+
+        match output {
+            Some(output) if problems.0.is_empty() => Ok((character, output)),
+            _ => Err(AnswersError(problems.0)),
+        }
+    }
+}
+
+impl AttributeAnswers {
+    fn build(&self, problems: &mut Problems) -> Option<Attributes> {
+        let highest: Option<Attribute> = problems.parse("attributes.highest", &self.highest);
+        let lowest: Option<Attribute> = problems.parse("attributes.lowest", &self.lowest);
+        let threes: Vec<Option<Attribute>> = self
+            .threes
+            .iter()
+            .enumerate()
+            .map(|(i, attr)| problems.parse_str(format!("attributes.threes[{i}]"), attr))
+            .collect();
+        let threes: Option<Vec<Attribute>> = threes.into_iter().collect();
+        if self.threes.is_empty() {
+            problems.push("attributes.threes", "is missing");
+        }
+
+        let mut attributes = Attributes::default();
+        if let Err(e) = attributes.set_attributes_during_creation(highest?, lowest?, threes?) {
+            problems.push("attributes", e);
+            return None;
+        }
+        Some(attributes)
+    }
+}
+
+impl SkillAnswers {
+    fn build(&self, problems: &mut Problems) -> Option<Skills> {
+        let distribution: Option<SkillDistribution> =
+            problems.parse("skills.distribution", &self.distribution);
+        if self.tiers.is_empty() {
+            problems.push("skills.tiers", "is missing");
+        }
+        let mut tiers = Some(Vec::new());
+        for (i, tier) in self.tiers.iter().enumerate() {
+            let tier: Option<Vec<Skill>> = tier
+                .iter()
+                .enumerate()
+                .map(|(j, skill)| problems.parse_str(format!("skills.tiers[{i}][{j}]"), skill))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .collect();
+            tiers = tiers.zip(tier).map(|(mut tiers, tier)| {
+                tiers.push(tier);
+                tiers
+            });
+        }
+
+        let mut skills = Skills::default();
+        if let Err(e) = skills.set_skills_during_creation(distribution?, tiers?) {
+            problems.push("skills.tiers", e);
+            return None;
+        }
+
+        let required = skills.required_specialties();
+        for (skill, specialty) in &self.specialties {
+            let field = format!("skills.specialties.{skill}");
+            let Some(skill) = problems.parse_str::<Skill>(field.clone(), skill) else {
+                continue;
+            };
+            if !required.contains(&skill) {
+                problems.push(
+                    field,
+                    format!("{skill:?} doesn't need a specialty, use free_specialty instead"),
+                );
+            } else if let Err(e) = skills.add_specialty(skill, specialty) {
+                problems.push(field, e);
+            }
+        }
+        for skill in required {
+            if skills[skill].specialties.is_empty() {
+                problems.push(
+                    format!("skills.specialties.{}", skill.key()),
+                    format!("{skill:?} has dots and needs a specialty"),
+                );
+            }
+        }
+        match &self.free_specialty {
+            Some(free) => {
+                if let Some(skill) =
+                    problems.parse_str::<Skill>("skills.free_specialty.skill", &free.skill)
+                {
+                    if let Err(e) = skills.add_specialty(skill, &free.specialty) {
+                        problems.push("skills.free_specialty", e);
+                    }
+                }
+            }
+            None => problems.push("skills.free_specialty", "is missing"),
+        }
+        Some(skills)
+    }
+}
+
+impl PredatorAnswers {
+    fn apply(&self, character: &mut Character, problems: &mut Problems) {
+        let predator_type: Option<PredatorType> =
+            problems.parse("predator.type", &self.predator_type);
+        let specialty = problems.required("predator.specialty", &self.specialty);
+        // thin-bloods get no discipline from their predator type
+        let discipline = match &self.discipline {
+            None if character.clan == Some(Clan::ThinBlood) => Some(None),
+            discipline => problems
+                .required("predator.discipline", discipline)
+                .map(Some),
+        };
+        let (Some(predator_type), Some(specialty), Some(discipline)) =
+            (predator_type, specialty, discipline)
+        else {
+            return;
+        };
+
+        let Some((skill, name)) = specialty.strip_suffix(')').and_then(|s| s.split_once(" ("))
+        else {
+            problems.push(
+                "predator.specialty",
+                format!("write the skill and the specialty in parentheses, e.g. \"Brawl (Grappling)\", not \"{specialty}\""),
+            );
+            return;
+        };
+        let Some(skill) = problems.parse_str::<Skill>("predator.specialty", skill.trim()) else {
+            return;
+        };
+        // offered specialties may be written in any case
+        let name = predator_type
+            .specialties()
+            .iter()
+            .find_map(|&(s, offered)| match offered {
+                OfferedSpecialty::Named(n) if s == skill && n.eq_ignore_ascii_case(name) => Some(n),
+                _ => None,
+            })
+            .unwrap_or(name);
+        let discipline = match discipline {
+            None => None,
+            Some(discipline) => match predator_type
+                .disciplines(character.clan)
+                .into_iter()
+                .find(|d| d.to_string().eq_ignore_ascii_case(&discipline))
+            {
+                Some(discipline) => Some(discipline),
+                None => {
+                    problems.push(
+                        "predator.discipline",
+                        format!("{predator_type} doesn't offer the discipline {discipline}"),
+                    );
+                    return;
+                }
+            },
+        };
+        match predator_type.apply(character, (skill, name), discipline) {
+            Ok(()) => {}
+            Err(PredatorChoiceError::Specialty) => problems.push(
+                "predator.specialty",
+                format!("{predator_type} doesn't offer the specialty {specialty}"),
+            ),
+            Err(e) => problems.push("predator", e),
+        }
+    }
+}
+
+/// Problems found in the answers so far, each starting with the answer it is about.
+#[derive(Default)]
+struct Problems(Vec<String>);
+
+impl Problems {
+    fn push(&mut self, field: impl Display, problem: impl Display) {
+        self.0.push(format!("{field}: {problem}"));
+    }
+
+    fn required<T: Clone>(&mut self, field: &str, value: &Option<T>) -> Option<T> {
+        if value.is_none() {
+            self.push(field, "is missing");
+        }
+        value.clone()
+    }
+
+    fn parse<T>(&mut self, field: &str, value: &Option<String>) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.required(field, value)?;
+        self.parse_str(field, &value)
+    }
+
+    fn parse_str<T>(&mut self, field: impl Display, value: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.push(field, format!("{e} (\"{value}\")"));
+                None
+            }
+        }
+    }
+}
+
+/// An error that lists every missing or invalid answer.
+#[derive(Debug, PartialEq)]
+pub struct AnswersError(pub Vec<String>);
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the answers have {} problem(s):", self.0.len())?;
+        for problem in &self.0 {
+            write!(f, "\n    {problem}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &str = r#"
+player_name = "Jane"
+character_name = "Alexandra"
+chronicle = "Test Chronicle by Night"
+clan = "Toreador"
+sea_of_time = "ancilla"
+generation = 11
+output = "alexandra.json"
+
+[attributes]
+highest = "charisma"
+lowest = "s"
+threes = ["dexterity", "wits", "composure"]
+
+[skills]
+distribution = "balanced"
+tiers = [
+    ["performance", "persuasion", "etiquette"],
+    ["insight", "awareness", "subterfuge", "academics", "athletics"],
+    ["brawl", "drive", "stealth", "occult", "streetwise", "politics", "finance"],
+]
+free_specialty = { skill = "persuasion", specialty = "Flattery" }
+
+[skills.specialties]
+performance = "Dancing"
+academics = "Art History"
+
+[predator]
+type = "Siren"
+specialty = "Persuasion (Seduction)"
+discipline = "Presence"
+
+[[advantages]]
+kind = "merit"
+name = "Linguistics"
+dots = 1
+
+[[advantages]]
+kind = "background"
+name = "Resources"
+dots = 3
+
+[[advantages]]
+kind = "flaw"
+name = "Enemy"
+dots = 2
+note = "a jealous rival"
+
+[[advantages]]
+kind = "flaw"
+name = "Dark Secret"
+dots = 2
+"#;
+
+    #[test]
+    fn complete_answers_build_a_character() {
+        let answers: CreationAnswers = toml::from_str(ANSWERS).unwrap();
+        let (character, output) = answers.build().unwrap();
+
+        assert_eq!(output, PathBuf::from("alexandra.json"));
+        assert_eq!(character.clan, Some(Clan::Toreador));
+        assert_eq!(character.attributes.charisma.value(), 4);
+        assert_eq!(character.skills.performance.dots.value(), 3);
+        assert!(character.skills.academics.has_specialty("art history"));
+        assert!(character.skills.persuasion.has_specialty("Seduction"));
+        assert_eq!(character.generation, 11.into());
+        assert_eq!(character.experience.available(), 35);
+        assert_eq!(character.predator_type, Some(PredatorType::Siren));
+    }
+
+    #[test]
+    fn every_missing_answer_is_listed() {
+        let error = CreationAnswers::default().build().unwrap_err();
+
+        for field in [
+            "player_name",
+            "clan",
+            "attributes.highest",
+            "attributes.threes",
+            "skills.distribution",
+            "skills.tiers",
+            "generation",
+            "predator.type",
+            "output",
+        ] {
+            assert!(
+                error.0.contains(&format!("{field}: is missing")),
+                "{field} not reported in {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn advantage_dots_are_checked() {
+        let mut answers: CreationAnswers = toml::from_str(ANSWERS).unwrap();
+        answers.advantages[0].dots = 0;
+        answers.advantages[1].kind = String::from("perk");
+        answers.advantages[1].dots = 7;
+
+        let error = answers.build().unwrap_err();
+        assert_eq!(
+            error.0,
+            vec![
+                "advantages[0].dots: an advantage has between 1 and 5 dots, 0 is out of range",
+                "advantages[1].kind: string did not match merit, background or flaw (\"perk\")",
+                "advantages[1].dots: an advantage has between 1 and 5 dots, 7 is out of range",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_answers_are_listed() {
+        let mut answers: CreationAnswers = toml::from_str(ANSWERS).unwrap();
+        answers.attributes.threes[1] = String::from("charisma");
+        answers.skills.specialties.remove("performance");
+        answers.generation = Some(13);
+        answers.advantages.pop();
+
+        let error = answers.build().unwrap_err();
+        assert_eq!(
+            error.0,
+            vec![
+                "attributes: Charisma was picked more than once",
+                "skills.specialties.performance: Performance has dots and needs a specialty",
+                "generation: generation 13 doesn't fit a character who is ancilla, it must be between 10 and 11",
+                "advantages: 2 points of flaws taken, but 4 are required",
+            ]
+        );
+    }
+}
@@ -0,0 +1,497 @@
+use crate::character::advantages::{Advantage, AdvantageKind};
+use crate::character::attributes::Attributes;
+use crate::character::clan::Clan;
+use crate::character::disciplines::Discipline;
+use crate::character::predator::{OfferedSpecialty, PredatorType};
+use crate::character::sea_of_time::SeaOfTime;
+use crate::character::skills::{Skill, SkillDistribution, Skills};
+use crate::character::{Attribute, Character};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A rough idea of who a generated character is, which decides where their best
+/// attributes and skills go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Concept {
+    Bruiser,
+    Socialite,
+    Scholar,
+    Thief,
+    Occultist,
+}
+
+impl Concept {
+    pub const ALL: [Concept; 5] = [
+        Concept::Bruiser,
+        Concept::Socialite,
+        Concept::Scholar,
+        Concept::Thief,
+        Concept::Occultist,
+    ];
+
+    /// Attributes that get the most dots.
+    fn attributes(&self) -> &'static [Attribute] {
+        use Attribute::*;
+        match self {
+            Concept::Bruiser => &[Strength, Stamina, Dexterity, Resolve],
+            Concept::Socialite => &[Charisma, Manipulation, Composure, Wits],
+            Concept::Scholar => &[Intelligence, Resolve, Wits, Composure],
+            Concept::Thief => &[Dexterity, Wits, Composure, Manipulation],
+            Concept::Occultist => &[Intelligence, Resolve, Manipulation, Composure],
+        }
+    }
+
+    /// Skills that get the most dots.
+    fn skills(&self) -> &'static [Skill] {
+        use Skill::*;
+        match self {
+            Concept::Bruiser => &[Brawl, Athletics, Intimidation, Melee, Streetwise],
+            Concept::Socialite => &[Persuasion, Etiquette, Insight, Performance, Subterfuge],
+            Concept::Scholar => &[Academics, Investigation, Science, Medicine, Awareness],
+            Concept::Thief => &[Larceny, Stealth, Streetwise, Athletics, Subterfuge],
+            Concept::Occultist => &[Occult, Academics, Awareness, Insight, Investigation],
+        }
+    }
+}
+
+impl Display for Concept {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromStr for Concept {
+    type Err = ParseConceptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_lowercase().as_str() {
+            "bruiser" => Concept::Bruiser,
+            "socialite" => Concept::Socialite,
+            "scholar" => Concept::Scholar,
+            "thief" => Concept::Thief,
+            "occultist" => Concept::Occultist,
+            _ => return Err(ParseConceptError),
+        })
+    }
+}
+
+/// An error that occurs when a `Concept` should be parsed
+/// from a `String`, and the `String` has no adequate representation
+/// in any `Concept`.
+#[derive(Debug)]
+pub struct ParseConceptError;
+
+impl Display for ParseConceptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        "string did not match bruiser, socialite, scholar, thief or occultist".fmt(f)
+    }
+}
+
+impl std::error::Error for ParseConceptError {}
+
+/// What a generated character has to be; anything left out is picked at random.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    pub clan: Option<Clan>,
+    pub concept: Option<Concept>,
+}
+
+const FIRST_NAMES: [&str; 12] = [
+    "Ada", "Bastien", "Carmen", "Dmitri", "Esther", "Felix", "Greta", "Hugo", "Ines", "Jonah",
+    "Katya", "Lucius",
+];
+
+const LAST_NAMES: [&str; 12] = [
+    "Ashford",
+    "Blackwood",
+    "Crane",
+    "Delacroix",
+    "Everett",
+    "Fontaine",
+    "Grey",
+    "Holloway",
+    "Ivanova",
+    "Marsh",
+    "Quill",
+    "Sterling",
+];
+
+/// Merits and backgrounds a generated character spends their points on, up to three
+/// dots each.
+const ADVANTAGES: [(AdvantageKind, &str); 10] = [
+    (AdvantageKind::Merit, "Linguistics"),
+    (AdvantageKind::Background, "Allies"),
+    (AdvantageKind::Background, "Contacts"),
+    (AdvantageKind::Background, "Haven"),
+    (AdvantageKind::Background, "Herd"),
+    (AdvantageKind::Background, "Influence"),
+    (AdvantageKind::Background, "Mask"),
+    (AdvantageKind::Background, "Resources"),
+    (AdvantageKind::Background, "Retainers"),
+    (AdvantageKind::Background, "Status"),
+];
+
+/// Flaws of one point each that a generated character takes.
+const FLAWS: [&str; 6] = [
+    "Known Corpse",
+    "Known Blankbody",
+    "Shunned",
+    "Suspect",
+    "Creepy",
+    "Haunted",
+];
+
+/// Caitiff pick their disciplines from these at creation.
+const CAITIFF_DISCIPLINES: [Discipline; 9] = [
+    Discipline::Animalism,
+    Discipline::Auspex,
+    Discipline::Celerity,
+    Discipline::Dominate,
+    Discipline::Fortitude,
+    Discipline::Obfuscate,
+    Discipline::Potence,
+    Discipline::Presence,
+    Discipline::Protean,
+];
+
+/// The specialty a generated character takes in a skill.
+fn specialty(skill: Skill) -> &'static str {
+    match skill {
+        Skill::Athletics => "Running",
+        Skill::Brawl => "Grappling",
+        Skill::Craft => "Carpentry",
+        Skill::Drive => "Motorcycles",
+        Skill::Firearms => "Pistols",
+        Skill::Larceny => "Lockpicking",
+        Skill::Melee => "Knives",
+        Skill::Stealth => "Shadowing",
+        Skill::Survival => "Urban Exploration",
+        Skill::AnimalKen => "Dogs",
+        Skill::Etiquette => "Elysium",
+        Skill::Insight => "Lies",
+        Skill::Intimidation => "Threats",
+        Skill::Leadership => "Command",
+        Skill::Performance => "Singing",
+        Skill::Persuasion => "Bargaining",
+        Skill::Streetwise => "Black Market",
+        Skill::Subterfuge => "Impersonation",
+        Skill::Academics => "History",
+        Skill::Awareness => "Ambushes",
+        Skill::Finance => "Stock Market",
+        Skill::Investigation => "Forensics",
+        Skill::Medicine => "First Aid",
+        Skill::Occult => "Kindred Lore",
+        Skill::Politics => "City Government",
+        Skill::Science => "Chemistry",
+        Skill::Technology => "Hacking",
+    }
+}
+
+/// The given preferred items in random order, followed by all the others in random order.
+fn preferring<T: Copy + PartialEq>(all: &[T], preferred: &[T], rng: &mut impl Rng) -> Vec<T> {
+    let mut first = preferred.to_vec();
+    first.shuffle(rng);
+    let mut rest: Vec<T> = all.iter().copied().filter(|t| !first.contains(t)).collect();
+    rest.shuffle(rng);
+    first.extend(rest);
+    first
+}
+
+/// Generate a character that follows the creation rules, e.g. as an NPC.
+///
+/// The character gets attributes, skills and specialties, two in-clan disciplines at
+/// two dots and one, a predator type, and spends the full budget of merits,
+/// backgrounds and flaws. Discipline powers aren't picked, and the starting experience
+/// is left unspent.
+///
+/// The same `rng` state always gives the same character, so pass a seeded generator
+/// to get reproducible results.
+pub fn generate(constraints: &Constraints, rng: &mut impl Rng) -> Character {
+    let clan = constraints
+        .clan
+        .unwrap_or_else(|| *Clan::ALL.choose(rng).expect("there are clans"));
+
+    let attribute_order = preferring(
+        &Attribute::ALL,
+        constraints.concept.map_or(&[], |c| c.attributes()),
+        rng,
+    );
+    let mut attributes = Attributes::default();
+    attributes
+        .set_attributes_during_creation(
+            attribute_order[0],
+            attribute_order[8],
+            attribute_order[1..4].to_vec(),
+        )
+        .expect("every attribute is picked once");
+
+    let distribution = *SkillDistribution::ALL
+        .choose(rng)
+        .expect("there are skill distributions");
+    let mut skill_order = preferring(
+        &Skill::ALL,
+        constraints.concept.map_or(&[], |c| c.skills()),
+        rng,
+    )
+    .into_iter();
+    let tiers = distribution
+        .tiers()
+        .iter()
+        .map(|&(_, count)| skill_order.by_ref().take(count).collect())
+        .collect();
+    let mut skills = Skills::default();
+    skills
+        .set_skills_during_creation(distribution, tiers)
+        .expect("every skill is picked once");
+    for skill in skills.required_specialties() {
+        skills
+            .add_specialty(skill, specialty(skill))
+            .expect("required specialties are on skills with dots");
+    }
+    let trained: Vec<Skill> = Skill::ALL
+        .into_iter()
+        .filter(|&s| skills[s].dots.value() > 0 && !Skill::NEED_SPECIALTY.contains(&s))
+        .collect();
+    let free = *trained
+        .choose(rng)
+        .expect("every distribution trains skills");
+    skills
+        .add_specialty(free, specialty(free))
+        .expect("the free specialty is on a skill with dots");
+
+    let character_name = format!(
+        "{} {}",
+        FIRST_NAMES.choose(rng).expect("there are first names"),
+        LAST_NAMES.choose(rng).expect("there are last names")
+    );
+    let mut character = Character::builder()
+        .player_name(String::from("Storyteller"))
+        .character_name(character_name)
+        .chronicle(String::new())
+        .clan(clan)
+        .attributes(attributes)
+        .skills(skills)
+        // new characters start with Hunger 1 by default
+        .hunger(1.into())
+        .build();
+
+    // thin-bloods are always childer, and childer always thin-blooded
+    let sea_of_time = match clan {
+        Clan::ThinBlood => SeaOfTime::Childe,
+        _ => *[SeaOfTime::Neonate, SeaOfTime::Ancilla]
+            .choose(rng)
+            .expect("there are ages"),
+    };
+    let generation = rng.gen_range(sea_of_time.generations());
+    sea_of_time
+        .apply(&mut character, generation)
+        .expect("the generation is in range");
+
+    // thin-bloods have no clan disciplines
+    let clan_disciplines: &[Discipline] = match clan {
+        Clan::Caitiff => &CAITIFF_DISCIPLINES,
+        clan => clan.disciplines(),
+    };
+    let mut clan_disciplines = clan_disciplines.to_vec();
+    clan_disciplines.shuffle(rng);
+    for (&discipline, dots) in clan_disciplines.iter().zip([2, 1]) {
+        for _ in 0..dots {
+            character.disciplines.add_dot(discipline);
+        }
+    }
+
+    // only predator specialties in a skill the character has dots in, and not taken yet
+    let offers = |predator_type: &PredatorType| -> Vec<(Skill, &'static str)> {
+        predator_type
+            .available_specialties(&character.skills)
+            .into_iter()
+            .map(|(skill, offered)| match offered {
+                OfferedSpecialty::Named(name) => (skill, name),
+                OfferedSpecialty::Kind(_) => (skill, specialty(skill)),
+            })
+            .filter(|&(skill, name)| !character.skills[skill].has_specialty(name))
+            .collect()
+    };
+    let predator_types: Vec<PredatorType> = PredatorType::ALL
+        .into_iter()
+        .filter(|p| !offers(p).is_empty())
+        .collect();
+    let predator_type = *predator_types
+        .choose(rng)
+        .expect("every character has dots in some predator type's specialty skills");
+    let (skill, name) = *offers(&predator_type)
+        .choose(rng)
+        .expect("the predator type was picked for its available specialties");
+    // thin-bloods get no discipline from their predator type
+    let discipline = predator_type
+        .disciplines(character.clan)
+        .choose(rng)
+        .copied();
+    predator_type
+        .apply(&mut character, (skill, name), discipline)
+        .expect("the picks are offered by the predator type");
+
+    // skip what the predator type already granted
+    let taken = |character: &Character, name: &str| {
+        let advantages = &character.advantages;
+        advantages
+            .merits
+            .iter()
+            .chain(&advantages.backgrounds)
+            .chain(&advantages.flaws)
+            .any(|a| a.name == name)
+    };
+    let budget = sea_of_time.advantage_budget();
+    let mut advantages: Vec<(AdvantageKind, &str)> = ADVANTAGES
+        .into_iter()
+        .filter(|&(_, name)| !taken(&character, name))
+        .collect();
+    advantages.shuffle(rng);
+    let mut points = budget.advantages;
+    for (i, &(kind, name)) in advantages.iter().enumerate() {
+        if points == 0 {
+            break;
+        }
+        // leave no more points than the remaining advantages can take
+        let rest = (advantages.len() - i - 1) as u8;
+        let dots = rng.gen_range(points.saturating_sub(3 * rest).max(1)..=points.min(3));
+        character
+            .advantages
+            .add(kind, Advantage::new(name, dots, None))
+            .expect("advantages get between 1 and 3 dots");
+        points -= dots;
+    }
+    let mut flaws: Vec<&str> = FLAWS
+        .into_iter()
+        .filter(|name| !taken(&character, name))
+        .collect();
+    flaws.shuffle(rng);
+    for name in flaws.into_iter().take(budget.flaws.into()) {
+        character
+            .advantages
+            .add(AdvantageKind::Flaw, Advantage::new(name, 1, None))
+            .expect("flaws get one dot");
+    }
+
+    character
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn same_seed_same_character() {
+        let constraints = Constraints::default();
+        let first = generate(&constraints, &mut StdRng::seed_from_u64(25));
+        let second = generate(&constraints, &mut StdRng::seed_from_u64(25));
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn generated_characters_are_legal() {
+        let constraints = Constraints {
+            clan: Some(Clan::Nosferatu),
+            concept: Some(Concept::Thief),
+        };
+        for seed in 0..50 {
+            let character = generate(&constraints, &mut StdRng::seed_from_u64(seed));
+            assert_eq!(character.clan, Some(Clan::Nosferatu));
+
+            let mut attributes: Vec<u8> = Attribute::ALL
+                .iter()
+                .map(|&a| character.attributes[a].value())
+                .collect();
+            attributes.sort();
+            assert_eq!(attributes, [1, 2, 2, 2, 2, 3, 3, 3, 4]);
+            assert!(character.attributes.dexterity.value() >= 3);
+
+            let dots: u32 = Skill::ALL
+                .iter()
+                .map(|&s| character.skills[s].dots.value() as u32)
+                .sum();
+            assert!([29, 26, 22].contains(&dots), "{dots} skill dots");
+            for skill in character.skills.required_specialties() {
+                assert!(!character.skills[skill].specialties.is_empty());
+            }
+
+            assert!(character.generation >= 10.into() && character.generation <= 13.into());
+
+            let budget = if character.generation <= 11.into() {
+                SeaOfTime::Ancilla.advantage_budget()
+            } else {
+                SeaOfTime::Neonate.advantage_budget()
+            };
+            assert_eq!(character.advantages.check_creation(&budget), Ok(()));
+            assert_eq!(
+                character.advantages.advantage_points(),
+                u32::from(budget.advantages)
+            );
+
+            let dots: u8 = character.disciplines.iter().map(|(_, r)| r.dots).sum();
+            assert_eq!(dots, 4);
+            assert!(character
+                .disciplines
+                .iter()
+                .any(|(&d, r)| r.dots >= 2 && Clan::Nosferatu.is_in_clan(d)));
+        }
+    }
+
+    #[test]
+    fn every_specialty_is_on_a_skill_with_dots() {
+        for seed in 0..100 {
+            let character = generate(&Constraints::default(), &mut StdRng::seed_from_u64(seed));
+            for skill in Skill::ALL {
+                let rating = &character.skills[skill];
+                assert!(
+                    rating.specialties.is_empty() || rating.dots.value() > 0,
+                    "{skill:?} has specialties without dots with seed {seed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn thin_bloods_are_childer() {
+        let constraints = Constraints {
+            clan: Some(Clan::ThinBlood),
+            concept: None,
+        };
+        let character = generate(&constraints, &mut StdRng::seed_from_u64(7));
+
+        assert!(character.generation >= 14.into());
+        assert_eq!(character.experience.available(), 0);
+        assert_eq!(character.blood_potency.value(), 0);
+        assert!(character.disciplines.is_empty());
+        assert_eq!(
+            character
+                .advantages
+                .check_creation(&SeaOfTime::Childe.advantage_budget()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn thin_blooded_blood_leeches_stay_at_blood_potency_0() {
+        let constraints = Constraints {
+            clan: Some(Clan::ThinBlood),
+            concept: Some(Concept::Bruiser),
+        };
+        let leeches: Vec<Character> = (0..200)
+            .map(|seed| generate(&constraints, &mut StdRng::seed_from_u64(seed)))
+            .filter(|c| c.predator_type == Some(PredatorType::BloodLeech))
+            .collect();
+
+        assert!(!leeches.is_empty());
+        for character in leeches {
+            assert_eq!(character.blood_potency.value(), 0);
+            assert!(character.disciplines.is_empty());
+        }
+    }
+}
@@ -0,0 +1,188 @@
+pub mod contest;
+pub mod odds;
+pub mod pool;
+
+use rand::Rng;
+use std::fmt;
+use std::fmt::Display;
+
+/// The dice of a single roll: regular dice and Hunger dice are kept apart, since
+/// Hunger dice showing a 10 or a 1 change the outcome; see Core Rulebook, p. 120ff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Roll {
+    pub dice: Vec<u8>,
+    pub hunger_dice: Vec<u8>,
+}
+
+impl Roll {
+    /// Roll a pool of d10s, `hunger` of which are Hunger dice. If Hunger exceeds the
+    /// pool, every die is a Hunger die.
+    ///
+    /// The random number generator is passed in so that rolls can be reproduced,
+    /// e.g. with a seeded `rand::rngs::StdRng`.
+    pub fn new(pool: u8, hunger: u8, rng: &mut impl Rng) -> Self {
+        let hunger = hunger.min(pool);
+        let mut d10 = || rng.gen_range(1..=10);
+        Self {
+            dice: (0..pool - hunger).map(|_| d10()).collect(),
+            hunger_dice: (0..hunger).map(|_| d10()).collect(),
+        }
+    }
+
+    fn all_dice(&self) -> impl Iterator<Item = &u8> {
+        self.dice.iter().chain(&self.hunger_dice)
+    }
+
+    fn tens(&self) -> usize {
+        self.all_dice().filter(|&&d| d == 10).count()
+    }
+
+    /// Every die showing 6 or more is a success, and every pair of 10s counts as
+    /// two extra successes.
+    pub fn successes(&self) -> u8 {
+        let successes = self.all_dice().filter(|&&d| d >= 6).count();
+        (successes + self.tens() / 2 * 2) as u8
+    }
+
+    /// At least one pair of 10s was rolled.
+    pub fn is_critical(&self) -> bool {
+        self.tens() >= 2
+    }
+
+    /// A critical where at least one of the 10s is on a Hunger die.
+    pub fn is_messy(&self) -> bool {
+        self.is_critical() && self.hunger_dice.contains(&10)
+    }
+
+    /// A Hunger die shows a 1, which turns a failure into a bestial failure.
+    pub fn has_bestial_one(&self) -> bool {
+        self.hunger_dice.contains(&1)
+    }
+
+    /// Classify the roll against the number of successes it needs.
+    pub fn outcome(&self, difficulty: u8) -> Outcome {
+        let successes = self.successes();
+        if successes >= difficulty {
+            if self.is_messy() {
+                Outcome::MessyCritical
+            } else if self.is_critical() {
+                Outcome::CriticalWin
+            } else {
+                Outcome::Win
+            }
+        } else if self.has_bestial_one() {
+            Outcome::BestialFailure
+        } else if successes == 0 {
+            Outcome::TotalFailure
+        } else {
+            Outcome::Failure
+        }
+    }
+}
+
+impl Display for Roll {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let dice: Vec<String> = self.dice.iter().map(|d| d.to_string()).collect();
+        write!(f, "[{}]", dice.join(" "))?;
+        if !self.hunger_dice.is_empty() {
+            let hunger: Vec<String> = self.hunger_dice.iter().map(|d| d.to_string()).collect();
+            write!(f, " hunger [{}]", hunger.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Win,
+    CriticalWin,
+    /// A critical win with a 10 on a Hunger die: the Beast gets its way.
+    MessyCritical,
+    Failure,
+    /// Not a single success.
+    TotalFailure,
+    /// A failed roll with a 1 on a Hunger die.
+    BestialFailure,
+}
+
+impl Outcome {
+    pub fn is_win(&self) -> bool {
+        matches!(
+            self,
+            Outcome::Win | Outcome::CriticalWin | Outcome::MessyCritical
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Win => "win",
+            Outcome::CriticalWin => "critical win",
+            Outcome::MessyCritical => "messy critical",
+            Outcome::Failure => "failure",
+            Outcome::TotalFailure => "total failure",
+            Outcome::BestialFailure => "bestial failure",
+        }
+        .fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn roll(dice: &[u8], hunger_dice: &[u8]) -> Roll {
+        Roll {
+            dice: dice.to_vec(),
+            hunger_dice: hunger_dice.to_vec(),
+        }
+    }
+
+    #[test]
+    fn seeded_rolls_are_reproducible() {
+        let first = Roll::new(7, 2, &mut StdRng::seed_from_u64(42));
+        let second = Roll::new(7, 2, &mut StdRng::seed_from_u64(42));
+
+        assert_eq!(first, second);
+        assert_eq!(first.dice.len(), 5);
+        assert_eq!(first.hunger_dice.len(), 2);
+        assert!(first.all_dice().all(|d| (1..=10).contains(d)));
+    }
+
+    #[test]
+    fn hunger_is_capped_at_the_pool() {
+        let roll = Roll::new(2, 4, &mut StdRng::seed_from_u64(1));
+        assert!(roll.dice.is_empty());
+        assert_eq!(roll.hunger_dice.len(), 2);
+    }
+
+    #[test]
+    fn pairs_of_tens_count_double() {
+        assert_eq!(roll(&[10, 10, 6, 3], &[]).successes(), 5);
+        assert_eq!(roll(&[10, 10, 10], &[]).successes(), 5);
+        assert_eq!(roll(&[10, 2], &[10]).successes(), 4);
+    }
+
+    #[test]
+    fn outcomes() {
+        assert_eq!(roll(&[6, 7, 2], &[]).outcome(2), Outcome::Win);
+        assert_eq!(roll(&[10, 10], &[]).outcome(3), Outcome::CriticalWin);
+        assert_eq!(roll(&[10, 3], &[10]).outcome(3), Outcome::MessyCritical);
+        assert_eq!(roll(&[6, 3], &[]).outcome(2), Outcome::Failure);
+        assert_eq!(roll(&[2, 3], &[5]).outcome(1), Outcome::TotalFailure);
+        assert_eq!(roll(&[2, 3], &[1]).outcome(1), Outcome::BestialFailure);
+    }
+
+    #[test]
+    fn hunger_ones_only_matter_on_failures() {
+        assert_eq!(roll(&[8, 9], &[1]).outcome(2), Outcome::Win);
+    }
+
+    #[test]
+    fn a_failed_critical_is_not_messy() {
+        assert_eq!(roll(&[10], &[10]).outcome(5), Outcome::Failure);
+    }
+}
@@ -0,0 +1,159 @@
+use crate::dice::pool::DicePool;
+use crate::dice::{Outcome, Roll};
+use rand::Rng;
+use std::fmt;
+use std::fmt::Display;
+
+/// What a roll is up against: a fixed difficulty set by the Storyteller, or another
+/// character's pool, e.g. Dexterity + Stealth against Wits + Awareness.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Opposition {
+    Difficulty(u8),
+    Pool(DicePool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Active,
+    Opposing,
+}
+
+/// A resolved roll against an [Opposition]; see Core Rulebook, p. 120ff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contest {
+    pub active: Roll,
+    /// The opposing roll, if the opposition was a pool.
+    pub opposing: Option<Roll>,
+    /// Successes of the active side minus the difficulty or the opposing successes.
+    pub margin: i16,
+    /// `None` for a tie between two pools, which keeps the status quo.
+    pub winner: Option<Side>,
+    /// The outcome for the active side, including messy criticals and bestial
+    /// failures from its Hunger dice.
+    pub outcome: Outcome,
+}
+
+impl Contest {
+    /// Roll the active pool against the opposition. Against a difficulty, the active
+    /// side wins with at least as many successes; against a pool, it needs more.
+    pub fn resolve(active: &DicePool, opposition: &Opposition, rng: &mut impl Rng) -> Self {
+        let active = active.roll(rng);
+        let (opposing, to_beat, needed) = match opposition {
+            Opposition::Difficulty(difficulty) => (None, *difficulty, *difficulty),
+            Opposition::Pool(pool) => {
+                let roll = pool.roll(rng);
+                let successes = roll.successes();
+                (Some(roll), successes, successes + 1)
+            }
+        };
+
+        let margin = active.successes() as i16 - to_beat as i16;
+        let outcome = active.outcome(needed);
+        let winner = if outcome.is_win() {
+            Some(Side::Active)
+        } else if margin == 0 && opposing.is_some() {
+            None
+        } else {
+            Some(Side::Opposing)
+        };
+
+        Self {
+            active,
+            opposing,
+            margin,
+            winner,
+            outcome,
+        }
+    }
+}
+
+impl Display for Contest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} ({} successes, {})",
+            self.active,
+            self.active.successes(),
+            self.outcome
+        )?;
+        if let Some(opposing) = &self.opposing {
+            write!(
+                f,
+                " against {opposing} ({} successes)",
+                opposing.successes()
+            )?;
+        }
+        match self.winner {
+            Some(Side::Active) => write!(f, ", won by {}", self.margin),
+            Some(Side::Opposing) => write!(f, ", lost by {}", -self.margin),
+            None => write!(f, ", a tie"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dice::pool::PoolTerm;
+    use rand::rngs::mock::StepRng;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn pool(dice: i8, hunger: u8) -> DicePool {
+        DicePool {
+            terms: vec![PoolTerm::new("test", dice)],
+            hunger,
+        }
+    }
+
+    #[test]
+    fn difficulty_is_met_with_equal_successes() {
+        // every die shows a 10: 3 dice are 5 successes
+        let mut rng = StepRng::new(0xF000_0000, 0);
+
+        let contest = Contest::resolve(&pool(3, 0), &Opposition::Difficulty(5), &mut rng);
+        assert_eq!(contest.margin, 0);
+        assert_eq!(contest.winner, Some(Side::Active));
+        assert_eq!(contest.outcome, Outcome::CriticalWin);
+    }
+
+    #[test]
+    fn ties_between_pools_have_no_winner() {
+        let mut rng = StepRng::new(0xF000_0000, 0);
+
+        let contest = Contest::resolve(&pool(2, 1), &Opposition::Pool(pool(2, 0)), &mut rng);
+        assert_eq!(contest.margin, 0);
+        assert_eq!(contest.winner, None);
+        assert_eq!(contest.outcome, Outcome::Failure);
+    }
+
+    #[test]
+    fn hunger_outcomes_of_the_active_side() {
+        // every die shows a 1
+        let mut rng = StepRng::new(0, 0);
+
+        let contest = Contest::resolve(&pool(3, 2), &Opposition::Pool(pool(1, 0)), &mut rng);
+        assert_eq!(contest.winner, None);
+        assert_eq!(contest.outcome, Outcome::BestialFailure);
+
+        let mut rng = StepRng::new(0xF000_0000, 0);
+        let contest = Contest::resolve(&pool(3, 2), &Opposition::Pool(pool(1, 0)), &mut rng);
+        assert_eq!(contest.margin, 4);
+        assert_eq!(contest.winner, Some(Side::Active));
+        assert_eq!(contest.outcome, Outcome::MessyCritical);
+    }
+
+    #[test]
+    fn margin_matches_the_rolls() {
+        let mut rng = StdRng::seed_from_u64(18);
+        for _ in 0..10 {
+            let contest = Contest::resolve(&pool(5, 1), &Opposition::Pool(pool(4, 0)), &mut rng);
+            let opposing = contest.opposing.as_ref().unwrap();
+            assert_eq!(
+                contest.margin,
+                contest.active.successes() as i16 - opposing.successes() as i16
+            );
+            assert_eq!(contest.winner == Some(Side::Active), contest.margin > 0);
+        }
+    }
+}
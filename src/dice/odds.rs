@@ -0,0 +1,187 @@
+use crate::dice::pool::DicePool;
+use crate::dice::{Outcome, Roll};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Display;
+
+/// The chance of every [Outcome] for a pool against a difficulty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Odds {
+    pub win: f64,
+    pub critical_win: f64,
+    pub messy_critical: f64,
+    pub failure: f64,
+    pub total_failure: f64,
+    pub bestial_failure: f64,
+}
+
+/// What matters about a set of dice for its outcome: the successes that aren't 10s,
+/// the 10s, and whether any Hunger die shows a 10 or a 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Tally {
+    successes: u8,
+    tens: u8,
+    hunger_ten: bool,
+    hunger_one: bool,
+}
+
+impl Tally {
+    /// A roll with the same outcome as every roll with this tally.
+    fn as_roll(&self) -> Roll {
+        let regular_tens = self.tens - u8::from(self.hunger_ten);
+        let mut dice = vec![6; self.successes as usize];
+        dice.extend(vec![10; regular_tens as usize]);
+        let mut hunger_dice = Vec::new();
+        if self.hunger_ten {
+            hunger_dice.push(10);
+        }
+        if self.hunger_one {
+            hunger_dice.push(1);
+        }
+        Roll { dice, hunger_dice }
+    }
+}
+
+impl Odds {
+    /// Compute the exact odds by going through every combination of faces, one die at
+    /// a time; a die shows 1–5, 6–9 or 10, and only Hunger dice care about the 1.
+    pub fn new(pool: u8, hunger: u8, difficulty: u8) -> Self {
+        let hunger = hunger.min(pool);
+        let start = Tally {
+            successes: 0,
+            tens: 0,
+            hunger_ten: false,
+            hunger_one: false,
+        };
+        let mut tallies = HashMap::from([(start, 1.0)]);
+
+        for die in 0..pool {
+            let is_hunger = die < hunger;
+            let mut next = HashMap::new();
+            for (tally, chance) in tallies {
+                let mut add = |tally: Tally, faces: f64| {
+                    *next.entry(tally).or_insert(0.0) += chance * faces / 10.0;
+                };
+                if is_hunger {
+                    add(
+                        Tally {
+                            hunger_one: true,
+                            ..tally
+                        },
+                        1.0,
+                    );
+                    add(tally, 4.0);
+                } else {
+                    add(tally, 5.0);
+                }
+                add(
+                    Tally {
+                        successes: tally.successes + 1,
+                        ..tally
+                    },
+                    4.0,
+                );
+                add(
+                    Tally {
+                        tens: tally.tens + 1,
+                        hunger_ten: tally.hunger_ten || is_hunger,
+                        ..tally
+                    },
+                    1.0,
+                );
+            }
+            tallies = next;
+        }
+
+        let mut odds = Self::default();
+        for (tally, chance) in tallies {
+            *odds.chance_mut(tally.as_roll().outcome(difficulty)) += chance;
+        }
+        odds
+    }
+
+    pub fn for_pool(pool: &DicePool, difficulty: u8) -> Self {
+        Self::new(pool.size(), pool.hunger, difficulty)
+    }
+
+    fn chance_mut(&mut self, outcome: Outcome) -> &mut f64 {
+        match outcome {
+            Outcome::Win => &mut self.win,
+            Outcome::CriticalWin => &mut self.critical_win,
+            Outcome::MessyCritical => &mut self.messy_critical,
+            Outcome::Failure => &mut self.failure,
+            Outcome::TotalFailure => &mut self.total_failure,
+            Outcome::BestialFailure => &mut self.bestial_failure,
+        }
+    }
+
+    /// The chance to reach the difficulty at all.
+    pub fn success(&self) -> f64 {
+        self.win + self.critical_win + self.messy_critical
+    }
+}
+
+impl Display for Odds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rows = [
+            ("Success", self.success()),
+            ("  critical win", self.critical_win),
+            ("  messy critical", self.messy_critical),
+            ("Failure", 1.0 - self.success()),
+            ("  total failure", self.total_failure),
+            ("  bestial failure", self.bestial_failure),
+        ];
+        let rows: Vec<String> = rows
+            .iter()
+            .map(|(label, chance)| {
+                let label = format!("{label}:");
+                format!("{label:<19}{:>5.1}%", chance * 100.0)
+            })
+            .collect();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "{actual} is not {expected}"
+        );
+    }
+
+    #[test]
+    fn single_die() {
+        let odds = Odds::new(1, 0, 1);
+        assert_close(odds.win, 0.5);
+        assert_close(odds.total_failure, 0.5);
+
+        let odds = Odds::new(1, 1, 1);
+        assert_close(odds.win, 0.5);
+        assert_close(odds.total_failure, 0.4);
+        assert_close(odds.bestial_failure, 0.1);
+    }
+
+    #[test]
+    fn two_dice_criticals() {
+        // both dice show 10
+        let odds = Odds::new(2, 1, 4);
+        assert_close(odds.messy_critical, 0.01);
+        assert_close(odds.critical_win, 0.0);
+        assert_close(odds.success(), 0.01);
+
+        let odds = Odds::new(2, 0, 1);
+        assert_close(odds.critical_win, 0.01);
+        assert_close(odds.success(), 0.75);
+    }
+
+    #[test]
+    fn odds_add_up() {
+        let odds = Odds::new(7, 3, 3);
+        let total = odds.success() + odds.failure + odds.total_failure + odds.bestial_failure;
+        assert_close(total, 1.0);
+    }
+}
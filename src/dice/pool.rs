@@ -0,0 +1,262 @@
+use crate::character::attributes::AttributeCategory;
+use crate::character::skills::Skill;
+use crate::character::stats::Willpower;
+use crate::character::{Attribute, Character};
+use crate::dice::Roll;
+use rand::Rng;
+use std::fmt;
+use std::fmt::Display;
+
+/// A dice pool assembled from a character sheet, keeping every term that went into it
+/// so that the table can see where the dice come from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DicePool {
+    pub terms: Vec<PoolTerm>,
+    pub hunger: u8,
+}
+
+/// A single contribution to a [DicePool], e.g. `Dexterity 3` or `impaired (Health) -2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolTerm {
+    pub source: String,
+    pub dice: i8,
+}
+
+impl DicePool {
+    /// Build the pool for `attribute` + `skill`.
+    ///
+    /// The pool gets one extra die if the character has the given specialty in the
+    /// skill, and loses two dice if the character is impaired: physical pools by a
+    /// full Health track, social and mental pools by a full Willpower track, and all
+    /// pools by more stains than empty Humanity boxes. An active compulsion subtracts
+    /// its penalty if it affects the attribute; leave that term out for actions that
+    /// pursue the compulsion. The character's Hunger sets the number of Hunger dice.
+    pub fn from_character(
+        character: &Character,
+        attribute: Attribute,
+        skill: Skill,
+        specialty: Option<&str>,
+    ) -> Self {
+        let attribute_dots = character.attributes[attribute].value();
+        let skill_rating = &character.skills[skill];
+
+        let mut pool = Self {
+            terms: vec![
+                PoolTerm::new(format!("{attribute:?}"), attribute_dots as i8),
+                PoolTerm::new(format!("{skill:?}"), skill_rating.dots.value() as i8),
+            ],
+            hunger: character.hunger.value(),
+        };
+
+        if let Some(specialty) = specialty.filter(|s| skill_rating.has_specialty(s)) {
+            pool = pool.with_modifier(format!("specialty ({specialty})"), 1);
+        }
+
+        match attribute.category() {
+            AttributeCategory::Physical if character.health().is_impaired() => {
+                pool = pool.with_modifier("impaired (Health)", -2);
+            }
+            AttributeCategory::Social | AttributeCategory::Mental
+                if Willpower::from_character(character).is_impaired() =>
+            {
+                pool = pool.with_modifier("impaired (Willpower)", -2);
+            }
+            _ => {}
+        }
+        if character.humanity.is_impaired() {
+            pool = pool.with_modifier("impaired (Humanity)", -2);
+        }
+
+        pool.with_compulsion_penalty(character, attribute)
+    }
+
+    /// The pool to resist frenzy: current Willpower plus a third of Humanity, rounded
+    /// down; see Core Rulebook, p. 219. Resisting frenzy isn't an action, so an active
+    /// compulsion doesn't apply.
+    pub fn frenzy(character: &Character) -> Self {
+        Self {
+            terms: vec![
+                PoolTerm::new(
+                    "Willpower",
+                    Willpower::from_character(character).current() as i8,
+                ),
+                PoolTerm::new(
+                    "one third of Humanity",
+                    (character.humanity.value.value() / 3) as i8,
+                ),
+            ],
+            hunger: character.hunger.value(),
+        }
+    }
+
+    /// Add a situational bonus or penalty to the pool.
+    pub fn with_modifier(mut self, source: impl Into<String>, dice: i8) -> Self {
+        self.terms.push(PoolTerm::new(source, dice));
+        self
+    }
+
+    /// The number of dice to roll. Penalties never reduce a pool below one die.
+    pub fn size(&self) -> u8 {
+        self.terms
+            .iter()
+            .map(|t| t.dice as i16)
+            .sum::<i16>()
+            .clamp(1, u8::MAX as i16) as u8
+    }
+
+    /// Subtract the penalty of an active compulsion that affects `attribute` from the pool.
+    fn with_compulsion_penalty(self, character: &Character, attribute: Attribute) -> Self {
+        match character.clan.and_then(|c| c.compulsion()) {
+            Some(compulsion) if character.compulsion_active && compulsion.affects(attribute) => {
+                self.with_modifier(
+                    format!("compulsion ({})", compulsion.name),
+                    -(compulsion.penalty as i8),
+                )
+            }
+            _ => self,
+        }
+    }
+
+    pub fn roll(&self, rng: &mut impl Rng) -> Roll {
+        Roll::new(self.size(), self.hunger, rng)
+    }
+}
+
+impl PoolTerm {
+    pub fn new(source: impl Into<String>, dice: i8) -> Self {
+        Self {
+            source: source.into(),
+            dice,
+        }
+    }
+}
+
+impl Display for DicePool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            match (i, term.dice) {
+                (0, dice) => write!(f, "{} {dice}", term.source)?,
+                (_, dice) if dice < 0 => write!(f, " - {} {}", term.source, -dice)?,
+                (_, dice) => write!(f, " + {} {dice}", term.source)?,
+            }
+        }
+        write!(
+            f,
+            " = {} dice, {} of them Hunger dice",
+            self.size(),
+            self.hunger.min(self.size())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::clan::Clan;
+    use crate::character::skills::SkillRating;
+
+    fn test_character() -> Character {
+        let mut character = Character::builder()
+            .player_name(String::from("Test Player"))
+            .character_name(String::from("Test Character"))
+            .chronicle(String::from("Test Chronicle by Night"))
+            .hunger(2.into())
+            .build();
+        character.attributes.set_all_to_2();
+        character.attributes.dexterity = 3.into();
+        character.skills.stealth = SkillRating::new(2).with_specialty("Shadowing");
+        character
+    }
+
+    #[test]
+    fn pool_from_attribute_and_skill() {
+        let pool = DicePool::from_character(
+            &test_character(),
+            Attribute::Dexterity,
+            Skill::Stealth,
+            None,
+        );
+
+        assert_eq!(pool.size(), 5);
+        assert_eq!(pool.hunger, 2);
+        assert_eq!(
+            pool.to_string(),
+            "Dexterity 3 + Stealth 2 = 5 dice, 2 of them Hunger dice"
+        );
+    }
+
+    #[test]
+    fn matching_specialty_adds_a_die() {
+        let character = test_character();
+
+        let shadowing = DicePool::from_character(
+            &character,
+            Attribute::Dexterity,
+            Skill::Stealth,
+            Some("shadowing"),
+        );
+        let disguise = DicePool::from_character(
+            &character,
+            Attribute::Dexterity,
+            Skill::Stealth,
+            Some("Disguise"),
+        );
+
+        assert_eq!(shadowing.size(), 6);
+        assert_eq!(disguise.size(), 5);
+    }
+
+    #[test]
+    fn frenzy_pool_from_willpower_and_humanity() {
+        let mut character = test_character();
+        character.willpower_damage.superficial = 1;
+
+        let pool = DicePool::frenzy(&character);
+        assert_eq!(
+            pool.to_string(),
+            "Willpower 3 + one third of Humanity 2 = 5 dice, 2 of them Hunger dice"
+        );
+    }
+
+    #[test]
+    fn compulsion_only_hits_affected_pools() {
+        let mut character = test_character();
+        character.clan = Some(Clan::Gangrel);
+        character.compulsion_active = true;
+
+        let sneaking =
+            DicePool::from_character(&character, Attribute::Dexterity, Skill::Stealth, None);
+        let lying =
+            DicePool::from_character(&character, Attribute::Manipulation, Skill::Subterfuge, None);
+
+        assert_eq!(sneaking.size(), 5);
+        assert_eq!(
+            lying.terms.last(),
+            Some(&PoolTerm::new("compulsion (Feral Impulses)", -3))
+        );
+        assert_eq!(DicePool::frenzy(&character).size(), 6);
+    }
+
+    #[test]
+    fn impairment_only_hits_matching_pools() {
+        let mut character = test_character();
+        // Health is Stamina 2 + 3
+        character.damage.superficial = 5;
+
+        let physical =
+            DicePool::from_character(&character, Attribute::Dexterity, Skill::Stealth, None);
+        let mental = DicePool::from_character(&character, Attribute::Wits, Skill::Awareness, None);
+
+        assert_eq!(physical.size(), 3);
+        assert_eq!(
+            physical.terms.last(),
+            Some(&PoolTerm::new("impaired (Health)", -2))
+        );
+        assert_eq!(mental.size(), 2);
+
+        // Willpower is Composure 2 + Resolve 2
+        character.willpower_damage.aggravated = 4;
+        let mental = DicePool::from_character(&character, Attribute::Wits, Skill::Awareness, None);
+        assert_eq!(mental.size(), 1);
+    }
+}
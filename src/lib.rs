@@ -1,14 +1,31 @@
 pub mod character;
+pub mod conflict;
+pub mod creation;
+pub mod dice;
 
+use crate::character::advantages::{Advantage, AdvantageKind};
 use crate::character::attributes::Attributes;
-use crate::character::blood::BloodPotency;
+use crate::character::bonds::Date;
+use crate::character::clan::Clan;
+use crate::character::predator::{OfferedSpecialty, PredatorType};
+use crate::character::sea_of_time::SeaOfTime;
+use crate::character::skills::{Skill, SkillDistribution, Skills};
 use crate::character::stats::{Health, Willpower};
-use crate::character::Character;
+use crate::character::{Attribute, Character};
+use crate::conflict::{Attack, Conflict, Defence, Participant};
+use crate::creation::generate::{generate, Concept, Constraints};
+use crate::creation::CreationAnswers;
+use crate::dice::odds::Odds;
+use crate::dice::pool::DicePool;
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::error::Error;
+use std::fmt::Display;
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::{fs, io, path::Path};
 
 /// Vampire-Charact-rs
@@ -27,7 +44,72 @@ pub enum Commands {
     /// Print details of a character in a JSON file
     Print { path: Option<PathBuf> },
     /// Interactively create a new character
-    Create {},
+    Create {
+        /// Take the answers from a TOML or JSON file instead of asking for them
+        #[arg(long)]
+        from: Option<PathBuf>,
+    },
+    /// List the blood bonds of all characters in the directory
+    Bonds {
+        path: Option<PathBuf>,
+        /// Show the bonds as they are on this date (YYYY-MM-DD), default today
+        #[arg(long)]
+        on: Option<Date>,
+    },
+    /// Run a combat scene between the characters in the files
+    Conflict {
+        paths: Vec<PathBuf>,
+        /// Sheets of NPCs to take part, which are never written back
+        #[arg(long)]
+        npc: Vec<PathBuf>,
+    },
+    /// Compute the exact odds of a roll, for a number of dice or a pool from a sheet
+    Odds {
+        /// Successes needed
+        #[arg(long, default_value_t = 1)]
+        difficulty: u8,
+        /// Number of dice, if not taken from a sheet
+        #[arg(
+            long,
+            required_unless_present = "sheet",
+            conflicts_with = "sheet",
+            value_parser = clap::value_parser!(u8).range(1..)
+        )]
+        pool: Option<u8>,
+        /// Number of Hunger dice; with a sheet, defaults to the character's Hunger
+        #[arg(long, value_parser = clap::value_parser!(u8).range(..=5))]
+        hunger: Option<u8>,
+        /// Character sheet to take the pool from
+        #[arg(long, requires_all = ["attribute", "skill"])]
+        sheet: Option<PathBuf>,
+        #[arg(long)]
+        attribute: Option<Attribute>,
+        #[arg(long)]
+        skill: Option<Skill>,
+        #[arg(long)]
+        specialty: Option<String>,
+    },
+    /// Generate a random character that follows the creation rules, e.g. for an NPC
+    Generate {
+        /// Generate the same character again by using the same seed
+        #[arg(long)]
+        seed: Option<u64>,
+        #[arg(long)]
+        clan: Option<Clan>,
+        /// Bruiser, Socialite, Scholar, Thief or Occultist
+        #[arg(long)]
+        concept: Option<Concept>,
+        /// Save the character to this file instead of printing it
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Make a Rouse Check for a character and write the new Hunger back to the file
+    Rouse {
+        path: PathBuf,
+        /// Level of the discipline power the check is for, if any
+        #[arg(long)]
+        power_level: Option<u8>,
+    },
 }
 
 /// List character files found in a directory.
@@ -51,6 +133,23 @@ pub fn list_characters(path: PathBuf) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// List the blood bonds that are in effect on `date` for every character in a directory.
+pub fn list_bonds(path: PathBuf, date: Date) -> Result<()> {
+    for p in json_paths(path)? {
+        let character = match Character::from_file(&p) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error processing character sheet {}: {}", p.display(), e);
+                continue;
+            }
+        };
+        for bond in character.blood_bonds.on(&date) {
+            println!("{}: {bond}", character.character_name);
+        }
+    }
+    Ok(())
+}
+
 /// Print the most important character details:
 ///
 /// The name of the player, the character, and the chronicle.
@@ -59,6 +158,189 @@ pub fn print_character(path: PathBuf) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Where the dice for the `odds` command come from.
+pub enum OddsPool {
+    Dice {
+        pool: u8,
+        hunger: u8,
+    },
+    Sheet {
+        path: PathBuf,
+        attribute: Attribute,
+        skill: Skill,
+        specialty: Option<String>,
+        hunger: Option<u8>,
+    },
+}
+
+/// Print the exact odds of every outcome for a pool against a difficulty.
+///
+/// A pool needs at least one die, and there are at most 5 Hunger dice.
+pub fn print_odds(pool: OddsPool, difficulty: u8) -> Result<()> {
+    let odds = match pool {
+        OddsPool::Dice { pool, hunger } => {
+            if pool == 0 {
+                return Err(anyhow::anyhow!("a pool needs at least one die"));
+            }
+            check_hunger_dice(hunger)?;
+            // like Odds::new, never more Hunger dice than dice
+            let hunger = hunger.min(pool);
+            println!("{pool} dice, {hunger} of them Hunger dice, against difficulty {difficulty}:");
+            Odds::new(pool, hunger, difficulty)
+        }
+        OddsPool::Sheet {
+            path,
+            attribute,
+            skill,
+            specialty,
+            hunger,
+        } => {
+            let character = Character::from_file(path)?;
+            let mut pool =
+                DicePool::from_character(&character, attribute, skill, specialty.as_deref());
+            if let Some(hunger) = hunger {
+                check_hunger_dice(hunger)?;
+                pool.hunger = hunger;
+            }
+            println!("{pool}, against difficulty {difficulty}:");
+            Odds::for_pool(&pool, difficulty)
+        }
+    };
+    println!("{odds}");
+    Ok(())
+}
+
+fn check_hunger_dice(hunger: u8) -> Result<()> {
+    if hunger > 5 {
+        return Err(anyhow::anyhow!(
+            "{hunger} Hunger dice are too many, there are at most 5"
+        ));
+    }
+    Ok(())
+}
+
+/// Make a Rouse Check for the character in the file and save the result.
+pub fn rouse(path: PathBuf, power_level: Option<u8>) -> Result<()> {
+    let mut character = Character::from_file(&path)?;
+    let check = character.rouse_check(power_level, &mut rand::thread_rng());
+    let dice: Vec<String> = check.dice.iter().map(|d| d.to_string()).collect();
+    if dice.is_empty() {
+        println!("{}: {}", character.character_name, check.outcome);
+    } else {
+        println!(
+            "{} rolled {}: {}",
+            character.character_name,
+            dice.join(", then "),
+            check.outcome
+        );
+    }
+    character
+        .to_file(&path)
+        .context("Could not write character to file")
+}
+
+/// Run a conflict interactively, round by round, and write the results back to the
+/// character files if the user wants to.
+pub fn run_conflict(paths: Vec<PathBuf>, npcs: Vec<PathBuf>) -> Result<()> {
+    let mut participants = paths
+        .iter()
+        .map(Participant::from_file)
+        .collect::<Result<Vec<_>>>()?;
+    for npc in npcs {
+        participants.push(Participant::npc(Character::from_file(npc)?));
+    }
+    if participants.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "a conflict needs at least two participants"
+        ));
+    }
+    let mut conflict = Conflict::new(participants);
+    let mut rng = rand::thread_rng();
+
+    loop {
+        println!("Round {}", conflict.round);
+        loop {
+            let names: Vec<String> = conflict
+                .participants
+                .iter()
+                .map(|p| format!("{} ({})", p.name(), p.state()))
+                .collect();
+            let attacker = read_choice("Who attacks?", &names)?;
+            let attack = Attack {
+                attribute: read_parsed("Attack attribute")?,
+                skill: read_parsed("Attack skill")?,
+                specialty: None,
+                kind: read_parsed("[S]uperficial or [a]ggravated damage")?,
+                weapon: read_parsed("Damage the weapon adds (0 for none)")?,
+            };
+            let defender = read_choice("Who defends?", &names)?;
+            let defence = Defence {
+                attribute: read_parsed("Defence attribute")?,
+                skill: read_parsed("Defence skill")?,
+            };
+            match conflict.exchange(attacker, &attack, defender, &defence, &mut rng) {
+                Ok(exchange) => println!("{exchange}."),
+                Err(e) => println!("{e}."),
+            }
+            if !read_user_input("Another attack this round? [y/N]")?.eq_ignore_ascii_case("y") {
+                break;
+            }
+        }
+        if !read_user_input("Another round? [y/N]")?.eq_ignore_ascii_case("y") {
+            break;
+        }
+        conflict.next_round();
+    }
+
+    println!("After {} rounds:", conflict.round);
+    for (name, state) in conflict.summary() {
+        println!("    {name}: {state}");
+    }
+    if read_user_input("Write the results back to the character files? [y/N]")?
+        .eq_ignore_ascii_case("y")
+    {
+        conflict
+            .save()
+            .context("Could not write characters to their files")?;
+    }
+    Ok(())
+}
+
+/// Generate a random character and print it, or save it if there's an output file.
+pub fn generate_character(
+    constraints: Constraints,
+    seed: Option<u64>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let character = generate(&constraints, &mut StdRng::seed_from_u64(seed));
+    println!("Generated with seed {seed}.");
+    match output {
+        Some(path) => character
+            .to_file(path)
+            .context("Could not write character to file"),
+        None => {
+            character.print();
+            Ok(())
+        }
+    }
+}
+
+/// Create a character from a file with all the answers the interactive creation asks for.
+pub fn create_character_from(path: PathBuf) -> Result<()> {
+    let answers = CreationAnswers::from_file(&path)?;
+    let (character, output) = answers.build()?;
+    character
+        .to_file(&output)
+        .context("Could not write character to file")?;
+    println!(
+        "Created {} and saved them to {}.",
+        character.character_name,
+        output.display()
+    );
+    Ok(())
+}
+
 /// Create a character by interactively providing the fields it requires.
 pub fn create_character() -> Result<()> {
     let attribute_selection_prompt: &str = "
@@ -80,80 +362,241 @@ pub fn create_character() -> Result<()> {
 
     println!("Thanks!");
 
-    println!("Now we need to distribute your attributes.");
-
-    println!("Select one attribute to assign 4 dots to, by typing the whole name or just the highlighted letter:");
-    let highest = read_user_input(attribute_selection_prompt)?.parse::<character::Attribute>()?;
-    println!("{:?} selected for 4 dots.", highest);
+    let clan = read_user_input(
+        "Which clan does your character belong to?
+    Banu Haqim, Brujah, Gangrel, Hecata, Lasombra, Malkavian, Ministry,
+    Nosferatu, Ravnos, Salubri, Toreador, Tremere, Tzimisce, Ventrue,
+    Caitiff or Thin-blood",
+    )?
+    .parse::<Clan>()?;
+    println!("{clan} selected.");
     println!();
 
-    println!("Now select the attribute to only assign 1 dot to:");
-    let lowest = read_user_input(attribute_selection_prompt)?.parse::<character::Attribute>()?;
-    println!("{:?} selected for 1 dot.", lowest);
-    println!();
+    println!("Now we need to distribute your attributes.");
 
-    println!("Now select three attributes with 3 dots each.");
-    println!("First 3-dot attribute:");
-    let _3_dots_1 = read_user_input(attribute_selection_prompt)?.parse::<character::Attribute>()?;
-    println!("{:?} selected for 3 dots.", _3_dots_1);
-    println!("Second 3-dot attribute:");
-    let _3_dots_2 = read_user_input(attribute_selection_prompt)?.parse::<character::Attribute>()?;
-    println!("{:?} selected for 3 dots.", _3_dots_2);
-    println!("Third 3-dot attribute:");
-    let _3_dots_3 = read_user_input(attribute_selection_prompt)?.parse::<character::Attribute>()?;
-    println!("{:?} selected for 3 dots.", _3_dots_3);
-
-    // map the highest, lowest, 3-dot and 2-dot attributes to the attribute enum
-    let mut attributes = Attributes::default();
-    attributes.set_attributes_during_creation(
-        highest,
-        lowest,
-        vec![_3_dots_1, _3_dots_2, _3_dots_3],
-    );
+    let attributes = loop {
+        println!("Select one attribute to assign 4 dots to, by typing the whole name or just the highlighted letter:");
+        let highest: Attribute = read_parsed(attribute_selection_prompt)?;
+        println!("{:?} selected for 4 dots.", highest);
+        println!();
 
-    println!();
+        println!("Now select the attribute to only assign 1 dot to:");
+        let lowest: Attribute = read_parsed(attribute_selection_prompt)?;
+        println!("{:?} selected for 1 dot.", lowest);
+        println!();
 
-    // TODO: choose skill distribution and then consequently skills
+        println!("Now select three attributes with 3 dots each.");
+        println!("First 3-dot attribute:");
+        let _3_dots_1: Attribute = read_parsed(attribute_selection_prompt)?;
+        println!("{:?} selected for 3 dots.", _3_dots_1);
+        println!("Second 3-dot attribute:");
+        let _3_dots_2: Attribute = read_parsed(attribute_selection_prompt)?;
+        println!("{:?} selected for 3 dots.", _3_dots_2);
+        println!("Third 3-dot attribute:");
+        let _3_dots_3: Attribute = read_parsed(attribute_selection_prompt)?;
+        println!("{:?} selected for 3 dots.", _3_dots_3);
 
-    //TODO implement sea of time later if we even need it at all
-    /*
-        {
-            let sea_of_time_choice = read_user_input(r#"Please choose whether you'll be playing as a
-
-    1. "childe": Embraced within the last 15 years, 14th generation or higher (thin-bloods), blood potency 0;\
-    2. "neonate": embraced between 1940 and about a decade ago, 12th or 13th generation, blood potency 1, 15 free XP;
-    3. "ancilla": embraced between 1780 and 1940, 10th or 11th generation, blood potency 2, additional 2 points of Advantages, 2 of Flaws, -1 to Humanity, 35 free XP
+        // map the highest, lowest, 3-dot and 2-dot attributes to the attribute enum
+        let mut attributes = Attributes::default();
+        match attributes.set_attributes_during_creation(
+            highest,
+            lowest,
+            vec![_3_dots_1, _3_dots_2, _3_dots_3],
+        ) {
+            Ok(()) => break attributes,
+            Err(e) => println!("{e}, please distribute your attributes again."),
+        }
+    };
 
-    Please type one of the quoted terms:
-    "#
-            )?;
+    println!();
 
-            match sea_of_time_choice {
-                "childe" => todo!(),
-                "neonate" => todo!(),
-                "ancilla" => todo!(),
-                _ => format_err!("You did not pick one of the allowed choices!")
-            };
+    let distribution = SkillDistribution::ALL[read_choice(
+        "Now choose how to distribute your skill dots:",
+        &SkillDistribution::ALL
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>(),
+    )?];
+    println!("{distribution} selected.");
+    let mut tiers: Vec<Vec<Skill>> = Vec::new();
+    for &(dots, count) in distribution.tiers() {
+        println!("Select {count} skills to assign {dots} dots to, by typing the skill's name:");
+        let mut tier = Vec::new();
+        while tier.len() < count {
+            let skill: Skill = read_parsed(&format!("Skill {} of {count}", tier.len() + 1))?;
+            if tiers.iter().flatten().chain(&tier).any(|s| *s == skill) {
+                println!("{skill:?} already has dots, please pick another skill.");
+                continue;
+            }
+            tier.push(skill);
         }
-    */
+        tiers.push(tier);
+    }
+    let mut skills = Skills::default();
+    skills.set_skills_during_creation(distribution, tiers)?;
+    println!();
 
-    //TODO refactor both generation parsing and blood potency from generation to own functions
-    let generation: u8 = read_user_input("Input the generation you would like to set for your character. Remember that *higher* generation number means weaker!")?
-        .parse()
-        .expect("couldn't read generation, did you input a number or text?");
+    for skill in skills.required_specialties() {
+        loop {
+            let specialty = read_user_input(&format!("{skill:?} needs a specialty"))?;
+            match skills.add_specialty(skill, specialty) {
+                Ok(()) => break,
+                Err(e) => println!("{e}, please try again."),
+            }
+        }
+    }
+    println!("You also get one free specialty in a skill of your choice.");
+    loop {
+        let skill: Skill = read_parsed("Skill for your free specialty")?;
+        let specialty = read_user_input(&format!("Specialty in {skill:?}"))?;
+        match skills.add_specialty(skill, specialty) {
+            Ok(()) => break,
+            Err(e) => println!("{e}, please try again."),
+        }
+    }
+    println!();
 
-    let blood_potency = BloodPotency::from_generation(&generation.into());
+    // thin-bloods are always childer, and childer always thin-blooded
+    let sea_of_time = if clan == Clan::ThinBlood {
+        println!("Thin-bloods are always childer, Embraced within the last 15 years, with Blood Potency 0.");
+        SeaOfTime::Childe
+    } else {
+        [SeaOfTime::Neonate, SeaOfTime::Ancilla][read_choice(
+            "How long ago was your character Embraced?",
+            &[
+                String::from("neonate: between 1940 and about a decade ago, Blood Potency 1, 15 XP"),
+                String::from("ancilla: between 1780 and 1940, Blood Potency 2, 2 more points of advantages and flaws, -1 Humanity, 35 XP"),
+            ],
+        )?]
+    };
+    let generations = sea_of_time.generations();
+    let generation = loop {
+        let generation: u8 = read_parsed(&format!(
+            "Input your character's generation, from {} to {}. Remember that *higher* generation number means weaker!",
+            generations.start(),
+            generations.end()
+        ))?;
+        if generations.contains(&generation) {
+            break generation;
+        }
+        println!(
+            "{generation} is not between {} and {}, please try again.",
+            generations.start(),
+            generations.end()
+        );
+    };
 
-    let character = Character::builder()
+    let mut character = Character::builder()
         .player_name(input_player_name)
         .character_name(input_char_name)
         .chronicle(input_chronicle)
+        .clan(clan)
         .attributes(attributes)
+        .skills(skills)
         // new characters start with Hunger 1 by default
         .hunger(1.into())
-        .blood_potency(blood_potency)
-        .generation(generation.into())
         .build();
+    sea_of_time.apply(&mut character, generation)?;
+
+    let predator_type = loop {
+        let predator_type: PredatorType = read_parsed(
+            "How does your character hunt? Pick a predator type:
+    Alleycat, Bagger, Blood Leech, Cleaver, Consensualist,
+    Farmer, Osiris, Sandman, Scene Queen or Siren",
+        )?;
+        let specialties = predator_type.available_specialties(&character.skills);
+        if specialties.is_empty() {
+            println!(
+                "{predator_type} needs dots in one of its specialty skills, please pick another predator type."
+            );
+            continue;
+        }
+        println!("{predator_type} selected.");
+
+        let names: Vec<String> = specialties
+            .iter()
+            .map(|(skill, specialty)| format!("{skill:?} ({specialty})"))
+            .collect();
+        let (skill, offered) = specialties[read_choice("Which specialty do you take?", &names)?];
+        let specialty = match offered {
+            OfferedSpecialty::Named(name) => name.to_owned(),
+            OfferedSpecialty::Kind(_) => {
+                read_user_input(&format!("Name your {skill:?} specialty ({offered})"))?
+            }
+        };
+
+        let disciplines = predator_type.disciplines(character.clan);
+        let discipline = if disciplines.is_empty() {
+            println!("Thin-bloods get no discipline dot from their predator type.");
+            None
+        } else {
+            let discipline_names: Vec<String> = disciplines.iter().map(|d| d.to_string()).collect();
+            Some(
+                disciplines
+                    [read_choice("Which discipline do you get a dot in?", &discipline_names)?],
+            )
+        };
+
+        match predator_type.apply(&mut character, (skill, &specialty), discipline) {
+            Ok(()) => break predator_type,
+            Err(e) => println!("{e}, please try again."),
+        }
+    };
+    let granted = predator_type.advantages();
+    for advantage in granted
+        .merits
+        .iter()
+        .chain(&granted.backgrounds)
+        .chain(&granted.flaws)
+    {
+        println!("Your predator type also grants {advantage}.");
+    }
+    println!();
+
+    let budget = sea_of_time.advantage_budget();
+    println!(
+        "Now choose your merits, backgrounds and flaws: you have {} points for merits and backgrounds, and have to take {} points of flaws.",
+        budget.advantages, budget.flaws
+    );
+    loop {
+        let kind = read_user_input(
+            "Add a [m]erit, [b]ackground or [f]law, or just press enter when you're done",
+        )?;
+        if kind.is_empty() {
+            match character.advantages.check_creation(&budget) {
+                Ok(()) => break,
+                Err(e) => {
+                    println!("{e}, please add some more.");
+                    continue;
+                }
+            }
+        }
+        let kind = match kind.parse::<AdvantageKind>() {
+            Ok(kind) => kind,
+            Err(e) => {
+                println!("{e}, please try again.");
+                continue;
+            }
+        };
+        let name = read_user_input("Name")?;
+        let Ok(dots) = read_user_input("Dots")?.parse::<u8>() else {
+            println!("That's not a number of dots, please try again.");
+            continue;
+        };
+        let note = read_user_input("Note (optional, e.g. 'criminals' for Contacts)")?;
+        let note = (!note.is_empty()).then_some(note);
+
+        let mut advantages = character.advantages.clone();
+        if let Err(e) = advantages.add(kind, Advantage::new(name, dots, note)) {
+            println!("{e}, please try again.");
+            continue;
+        }
+        match advantages.check_budget(&budget) {
+            Ok(()) => character.advantages = advantages,
+            Err(e) => println!("Can't add that: {e}."),
+        }
+    }
+    println!();
 
     let health = Health::from_character(&character, None, None);
 
@@ -172,6 +615,35 @@ pub fn create_character() -> Result<()> {
     Ok(())
 }
 
+/// Prompt the user until they pick one of the listed options, and return its index.
+fn read_choice(instruction: &str, options: &[String]) -> io::Result<usize> {
+    println!("{instruction}");
+    for (i, option) in options.iter().enumerate() {
+        println!("    {}. {option}", i + 1);
+    }
+    loop {
+        match read_user_input("Please type the number of your choice")?.parse::<usize>() {
+            Ok(choice @ 1..) if choice <= options.len() => return Ok(choice - 1),
+            Ok(choice) => println!("{choice} is not one of the offered choices, please try again."),
+            Err(_) => println!("That's not a number, please try again."),
+        }
+    }
+}
+
+/// Prompt the user until the input can be parsed.
+fn read_parsed<T>(instruction: &str) -> io::Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    loop {
+        match read_user_input(instruction)?.parse() {
+            Ok(value) => return Ok(value),
+            Err(e) => println!("{e}, please try again."),
+        }
+    }
+}
+
 /// Prompt the user to input something and return it as io::Result<String>.
 fn read_user_input(instruction: &str) -> io::Result<String> {
     print!("{instruction}: ");
@@ -1,6 +1,11 @@
 use clap::Parser;
 use std::process;
-use vampire_charact_rs::{create_character, list_characters, print_character};
+use vampire_charact_rs::character::bonds::Date;
+use vampire_charact_rs::creation::generate::Constraints;
+use vampire_charact_rs::{
+    create_character, create_character_from, generate_character, list_bonds, list_characters,
+    print_character, print_odds, rouse, run_conflict, OddsPool,
+};
 use vampire_charact_rs::{Cli, Commands};
 
 fn main() {
@@ -28,11 +33,75 @@ fn main() {
                 process::exit(1);
             }
         }
-        Commands::Create {} => {
-            if let Err(e) = create_character() {
+        Commands::Create { from } => {
+            let result = match from {
+                Some(path) => create_character_from(path),
+                None => create_character(),
+            };
+            if let Err(e) = result {
                 println!("Application error: {e}");
                 process::exit(1);
             }
         }
+        Commands::Bonds { path, on } => {
+            let p = path.unwrap_or_else(|| {
+                std::env::current_dir().expect("should be able to access its own directory")
+            });
+            if let Err(e) = list_bonds(p, on.unwrap_or_else(Date::today)) {
+                println!("Application error: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Conflict { paths, npc } => {
+            if let Err(e) = run_conflict(paths, npc) {
+                println!("Application error: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Odds {
+            difficulty,
+            pool,
+            hunger,
+            sheet,
+            attribute,
+            skill,
+            specialty,
+        } => {
+            let pool = match (sheet, attribute, skill) {
+                (Some(path), Some(attribute), Some(skill)) => OddsPool::Sheet {
+                    path,
+                    attribute,
+                    skill,
+                    specialty,
+                    hunger,
+                },
+                // clap makes sure that there's a pool without a sheet
+                _ => OddsPool::Dice {
+                    pool: pool.unwrap_or_default(),
+                    hunger: hunger.unwrap_or_default(),
+                },
+            };
+            if let Err(e) = print_odds(pool, difficulty) {
+                println!("Could not compute the odds: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Generate {
+            seed,
+            clan,
+            concept,
+            output,
+        } => {
+            if let Err(e) = generate_character(Constraints { clan, concept }, seed, output) {
+                println!("Application error: {e}");
+                process::exit(1);
+            }
+        }
+        Commands::Rouse { path, power_level } => {
+            if let Err(e) = rouse(path, power_level) {
+                println!("Could not make a Rouse Check: {e}");
+                process::exit(1);
+            }
+        }
     }
 }
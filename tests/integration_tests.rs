@@ -1,6 +1,11 @@
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use std::path::PathBuf;
 use vampire_charact_rs::character::attributes::Attributes;
-use vampire_charact_rs::character::skills::Skills;
+use vampire_charact_rs::character::clan::Clan;
+use vampire_charact_rs::character::disciplines::{Discipline, DisciplineRating, Power};
+use vampire_charact_rs::character::predator::PredatorType;
+use vampire_charact_rs::character::skills::{SkillRating, Skills};
 use vampire_charact_rs::character::stats::{Damage, Humanity};
 use vampire_charact_rs::character::{Attribute, Character};
 use vampire_charact_rs::*;
@@ -35,55 +40,98 @@ fn new_char_from_sample_json() {
         player_name: String::from("Jason"),
         character_name: String::from("Phil Rubens"),
         chronicle: String::from("Something by Night"),
+        clan: Some(Clan::Toreador),
+        predator_type: Some(PredatorType::Siren),
         attributes: Attributes {
-            strength: 1,
-            dexterity: 3,
-            stamina: 2,
-            charisma: 4,
-            manipulation: 3,
-            composure: 2,
-            intelligence: 2,
-            wits: 3,
-            resolve: 3,
+            strength: 1.into(),
+            dexterity: 3.into(),
+            stamina: 2.into(),
+            charisma: 4.into(),
+            manipulation: 3.into(),
+            composure: 2.into(),
+            intelligence: 2.into(),
+            wits: 3.into(),
+            resolve: 3.into(),
         },
         skills: Skills {
-            athletics: (0, None),
-            brawl: (0, None),
-            craft: (3, Some(String::from("plastic arts"))),
-            drive: (0, None),
-            firearms: (0, None),
-            larceny: (0, None),
-            melee: (0, None),
-            stealth: (1, None),
-            survival: (0, None),
-            animal_ken: (0, None),
-            etiquette: (3, None),
-            insight: (2, None),
-            intimidation: (1, None),
-            leadership: (2, None),
-            performance: (3, Some(String::from("public speaking"))),
-            persuasion: (3, Some(String::from("seduction"))),
-            streetwise: (0, None),
-            subterfuge: (2, None),
-            academics: (2, Some(String::from("art history"))),
-            awareness: (1, None),
-            finance: (2, Some(String::from("stock market"))),
-            investigation: (0, None),
-            medicine: (0, None),
-            occult: (1, None),
-            politics: (1, None),
-            science: (0, None),
-            technology: (0, None),
+            athletics: SkillRating::new(0),
+            brawl: SkillRating::new(0),
+            craft: SkillRating::new(3).with_specialty("plastic arts"),
+            drive: SkillRating::new(0),
+            firearms: SkillRating::new(0),
+            larceny: SkillRating::new(0),
+            melee: SkillRating::new(0),
+            stealth: SkillRating::new(1),
+            survival: SkillRating::new(0),
+            animal_ken: SkillRating::new(0),
+            etiquette: SkillRating::new(3),
+            insight: SkillRating::new(2),
+            intimidation: SkillRating::new(1),
+            leadership: SkillRating::new(2),
+            performance: SkillRating::new(3).with_specialty("public speaking"),
+            persuasion: SkillRating::new(3).with_specialty("seduction"),
+            streetwise: SkillRating::new(0),
+            subterfuge: SkillRating::new(2),
+            academics: SkillRating::new(2).with_specialty("art history"),
+            awareness: SkillRating::new(1),
+            finance: SkillRating::new(2).with_specialty("stock market"),
+            investigation: SkillRating::new(0),
+            medicine: SkillRating::new(0),
+            occult: SkillRating::new(1),
+            politics: SkillRating::new(1),
+            science: SkillRating::new(0),
+            technology: SkillRating::new(0),
         },
+        disciplines: [
+            (
+                Discipline::Auspex,
+                DisciplineRating {
+                    dots: 1,
+                    powers: vec![Power {
+                        name: String::from("Sense the Unseen"),
+                        level: None,
+                    }],
+                },
+            ),
+            (
+                Discipline::Celerity,
+                DisciplineRating {
+                    dots: 1,
+                    powers: vec![Power {
+                        name: String::from("Cat's Grace"),
+                        level: None,
+                    }],
+                },
+            ),
+            (
+                Discipline::Presence,
+                DisciplineRating {
+                    dots: 3,
+                    powers: ["Awe", "Daunt", "Lingering Kiss"]
+                        .map(|name| Power {
+                            name: String::from(name),
+                            level: None,
+                        })
+                        .to_vec(),
+                },
+            ),
+        ]
+        .into_iter()
+        .collect(),
+        advantages: Default::default(),
+        experience: Default::default(),
+        blood_bonds: Default::default(),
         damage: Damage::default(),
         willpower_damage: Damage::default(),
         humanity: Humanity {
-            value: 7,
+            value: 7.into(),
             stains: 1,
         },
         blood_potency: 1.into(),
         generation: 13.into(),
         hunger: 1.into(),
+        resonance: None,
+        compulsion_active: false,
     };
 
     let char = Character::from_file(PathBuf::from("tests/sample_character_dir/sample_char.json"))
@@ -98,22 +146,30 @@ fn new_char_from_sample_2() {
         player_name: String::from("Mary"),
         character_name: String::from("Cassandra Skyloft"),
         chronicle: String::from("Let the Streets Run Red"),
+        clan: None,
+        predator_type: None,
         attributes: Attributes {
-            strength: 1,
-            dexterity: 3,
-            stamina: 2,
-            charisma: 4,
-            manipulation: 3,
-            composure: 2,
-            intelligence: 2,
-            wits: 3,
-            resolve: 2,
+            strength: 1.into(),
+            dexterity: 3.into(),
+            stamina: 2.into(),
+            charisma: 4.into(),
+            manipulation: 3.into(),
+            composure: 2.into(),
+            intelligence: 2.into(),
+            wits: 3.into(),
+            resolve: 2.into(),
         },
         skills: Skills::default(),
+        disciplines: Default::default(),
+        advantages: Default::default(),
+        experience: Default::default(),
+        blood_bonds: Default::default(),
         damage: Default::default(),
         willpower_damage: Default::default(),
         humanity: Default::default(),
         hunger: 0.into(),
+        resonance: None,
+        compulsion_active: false,
         blood_potency: Default::default(),
         generation: 12.into(),
     };
@@ -132,9 +188,15 @@ fn new_char_from_sample_3() {
         player_name: String::from("Jib"),
         character_name: String::from("Mordred"),
         chronicle: String::from("Something by Night"),
+        clan: None,
+        predator_type: None,
 
         attributes: Attributes::default(),
         skills: Skills::default(),
+        disciplines: Default::default(),
+        advantages: Default::default(),
+        experience: Default::default(),
+        blood_bonds: Default::default(),
 
         damage: Damage {
             superficial: 3,
@@ -146,12 +208,14 @@ fn new_char_from_sample_3() {
         },
 
         humanity: Humanity {
-            value: 7,
+            value: 7.into(),
             stains: 1,
         },
         blood_potency: 2.into(),
         generation: 14.into(),
         hunger: 3.into(),
+        resonance: None,
+        compulsion_active: false,
     };
 
     let char = Character::from_file(PathBuf::from(
@@ -180,36 +244,38 @@ fn new_char_to_file() {
         Attribute::Manipulation,
     ];
 
-    attributes.set_attributes_during_creation(highest, lowest, three_mid);
+    attributes
+        .set_attributes_during_creation(highest, lowest, three_mid)
+        .unwrap();
 
     let skills = Skills {
-        athletics: (1, None),
-        brawl: (2, None),
-        craft: (3, Some(String::from("carpenter"))),
-        drive: (0, None),
-        firearms: (0, None),
-        larceny: (0, None),
-        melee: (1, None),
-        stealth: (0, None),
-        survival: (1, Some(String::from("foraging"))),
-        animal_ken: (2, None),
-        etiquette: (0, None),
-        insight: (1, None),
-        intimidation: (2, None),
-        leadership: (1, Some(String::from("practicality"))),
-        performance: (0, None),
-        persuasion: (1, None),
-        streetwise: (1, None),
-        subterfuge: (0, None),
-        academics: (0, None),
-        awareness: (3, None),
-        finance: (0, None),
-        investigation: (2, None),
-        medicine: (1, None),
-        occult: (0, None),
-        politics: (0, None),
-        science: (0, None),
-        technology: (0, None),
+        athletics: SkillRating::new(1),
+        brawl: SkillRating::new(2),
+        craft: SkillRating::new(3).with_specialty("carpenter"),
+        drive: SkillRating::new(0),
+        firearms: SkillRating::new(0),
+        larceny: SkillRating::new(0),
+        melee: SkillRating::new(1),
+        stealth: SkillRating::new(0),
+        survival: SkillRating::new(1).with_specialty("foraging"),
+        animal_ken: SkillRating::new(2),
+        etiquette: SkillRating::new(0),
+        insight: SkillRating::new(1),
+        intimidation: SkillRating::new(2),
+        leadership: SkillRating::new(1).with_specialty("practicality"),
+        performance: SkillRating::new(0),
+        persuasion: SkillRating::new(1),
+        streetwise: SkillRating::new(1),
+        subterfuge: SkillRating::new(0),
+        academics: SkillRating::new(0),
+        awareness: SkillRating::new(3),
+        finance: SkillRating::new(0),
+        investigation: SkillRating::new(2),
+        medicine: SkillRating::new(1),
+        occult: SkillRating::new(0),
+        politics: SkillRating::new(0),
+        science: SkillRating::new(0),
+        technology: SkillRating::new(0),
     };
 
     let bp = 2;
@@ -228,3 +294,40 @@ fn new_char_to_file() {
     ))
     .expect("couldn't write to test output file!");
 }
+
+#[test]
+fn sample_char_round_trip() {
+    let char = Character::from_file(PathBuf::from("tests/sample_character_dir/sample_char.json"))
+        .expect("sample_char.json should contain valid character json!");
+
+    let path = std::env::temp_dir().join("vampire_charact_rs_round_trip.json");
+    char.to_file(&path)
+        .expect("couldn't write to temporary output file!");
+    let reread = Character::from_file(&path).expect("written character should be readable");
+
+    assert_eq!(reread, char);
+}
+
+#[test]
+fn out_of_range_attribute_names_the_field() {
+    let error = Character::from_file(PathBuf::from("tests/faulty_char_sheet/strength_9.json"))
+        .expect_err("Strength 9 is out of range and should be rejected!");
+
+    assert!(error
+        .to_string()
+        .starts_with("attributes.strength: 9 is out of range"));
+}
+
+#[test]
+fn odds_need_a_pool_and_at_most_5_hunger_dice() {
+    let error = print_odds(OddsPool::Dice { pool: 0, hunger: 0 }, 1)
+        .expect_err("a pool of 0 dice should be rejected!");
+    assert_eq!(error.to_string(), "a pool needs at least one die");
+
+    let error = print_odds(OddsPool::Dice { pool: 3, hunger: 6 }, 1)
+        .expect_err("6 Hunger dice should be rejected!");
+    assert_eq!(
+        error.to_string(),
+        "6 Hunger dice are too many, there are at most 5"
+    );
+}